@@ -3,6 +3,8 @@ use amethyst::{
     audio::{AudioSink, Mp3Format, SourceHandle},
     ecs::{World, WorldExt},
 };
+
+use crate::config::WanderballConfig;
 use std::{iter::Cycle, vec::IntoIter};
 
 const OVERWORLD_THEME: &str = "audio/wanderball-overworld-theme.mp3";
@@ -27,7 +29,7 @@ pub fn start_audio(world: &mut World) {
     let music = {
         let loader = world.read_resource::<Loader>();
         let mut sink = world.write_resource::<AudioSink>();
-        sink.set_volume(0.20);
+        sink.set_volume(world.read_resource::<WanderballConfig>().music_volume);
 
         let music = MUSIC_TRACKS
             .iter()
@@ -41,3 +43,10 @@ pub fn start_audio(world: &mut World) {
 
     world.insert(music);
 }
+
+/// Turns the music up or down straight away, if there's anything to play it through
+pub fn set_music_volume(world: &World, volume: f32) {
+    if let Some(mut sink) = world.try_fetch_mut::<AudioSink>() {
+        sink.set_volume(volume);
+    }
+}
@@ -0,0 +1,416 @@
+use crate::components::{
+    path::{generate_path, segment_key, unique_segment_count},
+    shapes::circle::Circle,
+    wanderdata::Pedometer,
+};
+use crate::config::WanderballConfig;
+use crate::resources::{controls::Movement, graph::SegmentGraph, save::PathSegmentRecord};
+use crate::systems::ball::roll;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+
+/// The explorer runs at the same fixed rate the game aims for
+const FRAME_SECONDS: f32 = 1.0 / 60.0;
+
+/// Somewhere the ball sat without moving for too long
+#[derive(Debug, Clone)]
+pub struct Stuck {
+    pub frame: usize,
+    pub x: f32,
+    pub y: f32,
+    /// the segment it was trying to get to
+    pub heading_for: String,
+}
+
+/// Drives the first player's ball around the whole path on its own, depth first over the
+/// segment graph, heading back the way it came whenever it runs out of new segments
+#[derive(Debug, Clone, Default)]
+pub struct Autopilot {
+    pub active: bool,
+    /// where we've come from, the segment we're at or heading for on top
+    trail: Vec<usize>,
+    visited: HashSet<usize>,
+    /// the moves that get us to the top of the trail
+    moves: VecDeque<Movement>,
+    /// where the ball should be once the last move's been made
+    expected: Option<(f32, f32)>,
+    last_position: Option<(f32, f32)>,
+    still_frames: usize,
+    frame: usize,
+    pub stuck: Vec<Stuck>,
+    /// segments there's no way to roll onto from the segment next to them
+    pub unreachable: Vec<String>,
+    /// segments the ball can roll over but never counts as being on, because whatever was laid
+    /// down before them always wins
+    pub covered: Vec<String>,
+    pub finished: bool,
+}
+
+impl Autopilot {
+    pub fn new(active: bool) -> Autopilot {
+        Autopilot {
+            active,
+            ..Default::default()
+        }
+    }
+
+    /// Which way to push the ball this frame to carry on exploring from where it is, only rolling
+    /// over the segments that are `open`
+    #[allow(clippy::too_many_arguments)]
+    pub fn steer<F>(
+        &mut self,
+        graph: &SegmentGraph,
+        path_segments: &[PathSegmentRecord],
+        x: f32,
+        y: f32,
+        circle: &Circle,
+        stuck_frames: usize,
+        open: F,
+    ) -> Movement
+    where
+        F: Fn(&PathSegmentRecord) -> bool + Copy,
+    {
+        self.frame += 1;
+
+        if self.finished {
+            return Movement::default();
+        }
+
+        if self.trail.is_empty() {
+            match graph.node_at(x, y) {
+                Some(start) => {
+                    self.visited.insert(start);
+                    self.trail.push(start);
+                }
+                None => {
+                    self.finished = true;
+                    return Movement::default();
+                }
+            }
+        }
+
+        if self.last_position == Some((x, y)) {
+            self.still_frames += 1;
+        } else {
+            self.still_frames = 0;
+        }
+        self.last_position = Some((x, y));
+
+        // something other than us moved the ball, or it's not moving at all, so the plan's off
+        let off_course = self.expected.is_some_and(|expected| expected != (x, y));
+        if self.still_frames >= stuck_frames {
+            if let Some(target) = self.trail.last() {
+                self.stuck.push(Stuck {
+                    frame: self.frame,
+                    x,
+                    y,
+                    heading_for: graph.nodes[*target].key.clone(),
+                });
+            }
+            self.still_frames = 0;
+            self.moves.clear();
+            self.trail.pop();
+        } else if off_course {
+            self.moves.clear();
+        }
+        self.expected = None;
+
+        loop {
+            if let Some(movement) = self.moves.pop_front() {
+                let rolled = roll(x, y, circle, movement, path_segments, open);
+                self.expected = Some((rolled.x, rolled.y));
+                return movement;
+            }
+
+            let here = match self.trail.last() {
+                Some(here) => *here,
+                None => {
+                    self.finished = true;
+                    return Movement::default();
+                }
+            };
+
+            // make sure we're actually at the top of the trail before looking past it
+            if !graph.nodes[here].contains(x, y) {
+                match plan(graph, path_segments, circle, x, y, here, false, open) {
+                    Some(moves) => {
+                        self.moves = moves;
+                        continue;
+                    }
+                    None => {
+                        self.trail.pop();
+                        continue;
+                    }
+                }
+            }
+
+            // portals are left alone, the autopilot rolls the path as it was laid down
+            let visited = &self.visited;
+            let next = graph.neighbours(here).iter().copied().find(|neighbour| {
+                !visited.contains(neighbour) && !graph.is_portal(here, *neighbour)
+            });
+
+            match next {
+                Some(next) => {
+                    self.visited.insert(next);
+                    let key = graph.nodes[next].key.clone();
+                    if let Some(moves) = plan(graph, path_segments, circle, x, y, next, true, open)
+                    {
+                        self.trail.push(next);
+                        self.moves = moves;
+                    } else if let Some(moves) =
+                        plan(graph, path_segments, circle, x, y, next, false, open)
+                    {
+                        self.covered.push(key);
+                        self.trail.push(next);
+                        self.moves = moves;
+                    } else {
+                        self.unreachable.push(key);
+                    }
+                }
+                // nowhere new from here, head back the way we came
+                None => {
+                    self.trail.pop();
+                }
+            }
+        }
+    }
+}
+
+/// The quickest whole steps from where the ball is onto a segment, looking no further than the
+/// segments around it. When entering, the last step has to leave the ball counted as on the
+/// segment, otherwise just being inside it will do.
+#[allow(clippy::too_many_arguments)]
+fn plan<F>(
+    graph: &SegmentGraph,
+    path_segments: &[PathSegmentRecord],
+    circle: &Circle,
+    x: f32,
+    y: f32,
+    target: usize,
+    entering: bool,
+    open: F,
+) -> Option<VecDeque<Movement>>
+where
+    F: Fn(&PathSegmentRecord) -> bool + Copy,
+{
+    let node = &graph.nodes[target];
+    let reach = (node.half_width.max(node.half_height) * 4.0).ceil() as i32;
+    let directions = [
+        Movement {
+            move_x: Some(1.0),
+            move_y: None,
+        },
+        Movement {
+            move_x: Some(-1.0),
+            move_y: None,
+        },
+        Movement {
+            move_x: None,
+            move_y: Some(1.0),
+        },
+        Movement {
+            move_x: None,
+            move_y: Some(-1.0),
+        },
+    ];
+
+    // nothing further away than the edge of the search can get in the way, and leaving it out
+    // keeps each step cheap on long paths, the order's kept since the first segment found wins
+    let margin = reach as f32 + node.half_width.max(node.half_height) * 2.0;
+    let nearby: Vec<PathSegmentRecord> = path_segments
+        .iter()
+        .filter(|segment| {
+            (segment.transform.translation().x - node.x).abs() <= margin
+                && (segment.transform.translation().y - node.y).abs() <= margin
+        })
+        .cloned()
+        .collect();
+
+    // positions are counted in whole steps from where the ball is now
+    let offset = |i: i32, j: i32| (x + i as f32, y + j as f32);
+    let mut came_from: HashMap<(i32, i32), ((i32, i32), Movement)> = HashMap::new();
+    let mut queue = VecDeque::new();
+    queue.push_back((0, 0));
+    came_from.insert((0, 0), ((0, 0), Movement::default()));
+
+    while let Some((i, j)) = queue.pop_front() {
+        let (px, py) = offset(i, j);
+        for movement in &directions {
+            let rolled = roll(px, py, circle, *movement, &nearby, open);
+            if rolled.distance <= 0.0 {
+                continue;
+            }
+
+            let next = (
+                i + movement.move_x.unwrap_or(0.0) as i32,
+                j + movement.move_y.unwrap_or(0.0) as i32,
+            );
+
+            // the ball looks ahead as it rolls, so it can count as on a segment a little before
+            // it's inside it, and which segment it counts as on depends on which way it came in
+            let arrived = if entering {
+                rolled.stepped_on.last() == Some(&node.key)
+            } else {
+                node.contains(rolled.x, rolled.y)
+            };
+            if arrived {
+                let mut moves = VecDeque::new();
+                moves.push_front(*movement);
+                let mut current = (i, j);
+                while current != (0, 0) {
+                    let (previous, movement) = came_from[&current];
+                    moves.push_front(movement);
+                    current = previous;
+                }
+                return Some(moves);
+            }
+
+            if came_from.contains_key(&next) {
+                continue;
+            }
+
+            let target_center = ((node.x - x).round() as i32, (node.y - y).round() as i32);
+            if (next.0 - target_center.0).abs() > reach || (next.1 - target_center.1).abs() > reach
+            {
+                continue;
+            }
+
+            came_from.insert(next, ((i, j), *movement));
+            queue.push_back(next);
+        }
+    }
+
+    None
+}
+
+/// How an explorer run over a path went
+#[derive(Debug, Clone)]
+pub struct ExplorationReport {
+    pub seed: u64,
+    pub path_length: usize,
+    pub segment_count: usize,
+    pub frames: usize,
+    pub exploration: f32,
+    /// seconds until every segment had been rolled over, if that ever happened
+    pub time_to_full: Option<f32>,
+    pub stuck: Vec<Stuck>,
+    /// segments there's no way to roll onto from the segment next to them
+    pub unreachable: Vec<String>,
+    /// segments the ball can roll over but never counts as being on
+    pub covered: Vec<String>,
+    /// segments the ball never managed to roll onto, for whatever reason
+    pub never_entered: Vec<String>,
+}
+
+impl fmt::Display for ExplorationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "seed {} ({} segments, {} unique)",
+            self.seed, self.path_length, self.segment_count
+        )?;
+        writeln!(
+            f,
+            "explored {:.1}% in {} frames ({:.1}s)",
+            self.exploration,
+            self.frames,
+            self.frames as f32 * FRAME_SECONDS
+        )?;
+        match self.time_to_full {
+            Some(seconds) => writeln!(f, "time to 100%: {:.1}s", seconds)?,
+            None => writeln!(f, "time to 100%: never")?,
+        }
+        writeln!(f, "stuck {} times", self.stuck.len())?;
+        for stuck in &self.stuck {
+            writeln!(
+                f,
+                "  frame {} at ({}, {}) heading for {}",
+                stuck.frame, stuck.x, stuck.y, stuck.heading_for
+            )?;
+        }
+        for (label, segments) in &[
+            ("unreachable", &self.unreachable),
+            ("covered", &self.covered),
+            ("never entered", &self.never_entered),
+        ] {
+            writeln!(f, "{} segments {}", segments.len(), label)?;
+            for segment in segments.iter() {
+                writeln!(f, "  {}", segment)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Generates the path for a seed and lets the autopilot loose on it with no window, rendering or
+/// world, just the same path generation and ball movement the game uses. Surfaces, hazards,
+/// portals, gates and keys are all left out, so the report is for the bare path: a gate that
+/// stays locked or a conveyor the ball can't roll against can cut off segments it counts as
+/// reachable.
+pub fn explore(config: &WanderballConfig, seed: u64, path_length: usize) -> ExplorationReport {
+    let path_segments = generate_path(config, seed, path_length);
+    let graph = SegmentGraph::from_segments(&path_segments);
+    let circle = Circle::new(config.ball_radius);
+    let segment_count = unique_segment_count(&path_segments);
+
+    let mut pedometer = Pedometer {
+        segment_count,
+        ..Default::default()
+    };
+    let mut autopilot = Autopilot::new(true);
+    let mut time_to_full = None;
+    let (mut x, mut y) = (config.start_x, config.start_y);
+
+    // the ball starts out on the origin segment
+    if let Some(first) = path_segments.first() {
+        pedometer.step_on(segment_key(&first.transform));
+    }
+
+    let mut frames = 0;
+    while frames < config.autopilot_max_frames && !autopilot.finished {
+        frames += 1;
+
+        let movement = autopilot.steer(
+            &graph,
+            &path_segments,
+            x,
+            y,
+            &circle,
+            config.autopilot_stuck_frames,
+            |_| true,
+        );
+        let rolled = roll(x, y, &circle, movement, &path_segments, |_| true);
+        x = rolled.x;
+        y = rolled.y;
+        pedometer.distance += rolled.distance;
+        for key in rolled.stepped_on {
+            pedometer.step_on(key);
+        }
+
+        if time_to_full.is_none() && pedometer.visited.len() >= segment_count {
+            time_to_full = Some(frames as f32 * FRAME_SECONDS);
+        }
+    }
+
+    let mut never_entered: Vec<String> = graph
+        .nodes
+        .iter()
+        .map(|node| node.key.clone())
+        .filter(|key| !pedometer.visited.contains_key(key))
+        .collect();
+    never_entered.sort();
+
+    ExplorationReport {
+        seed,
+        path_length,
+        segment_count,
+        frames,
+        exploration: pedometer.exploration(),
+        time_to_full,
+        stuck: autopilot.stuck,
+        unreachable: autopilot.unreachable,
+        covered: autopilot.covered,
+        never_entered,
+    }
+}
@@ -0,0 +1,13 @@
+//! sends the autopilot round a path with no window, `explorer [seed]`
+
+extern crate amethyst;
+extern crate wanderball;
+
+fn main() -> amethyst::Result<()> {
+    let seed = match std::env::args().nth(1) {
+        Some(seed) => Some(seed.parse::<u64>()?),
+        None => None,
+    };
+
+    wanderball::explore(seed)
+}
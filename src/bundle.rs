@@ -5,7 +5,10 @@ use amethyst::{
 };
 
 use crate::systems::{
-    BallSystem, CoordinateSystem, PathSegmentSystem, PathSystem, VideographerSystem,
+    AutopilotSystem, BallSystem, CheckpointSystem, ClickSystem, CollectibleSystem, ControlsSystem,
+    CoordinateSystem, GhostSystem, GoalSystem, HazardSystem, HudSystem, KeySystem, LocaleSystem,
+    NpcSystem, PathSegmentSystem, PathSystem, PortalSystem, RaceSystem, RecordingSystem,
+    RouteSystem, SpeedrunSystem, StatsSystem, VideographerSystem,
 };
 
 #[derive(Default)]
@@ -17,11 +20,76 @@ impl<'a, 'b> SystemBundle<'a, 'b> for WanderballBundle {
         _world: &mut World,
         builder: &mut DispatcherBuilder<'a, 'b>,
     ) -> Result<(), Error> {
-        builder.add(BallSystem, "ball_system", &["input_system"]);
+        builder.add(ControlsSystem, "controls_system", &["input_system"]);
+        builder.add(AutopilotSystem, "autopilot_system", &["controls_system"]);
+        builder.add(ClickSystem, "click_system", &["input_system"]);
+        builder.add(
+            RouteSystem,
+            "route_system",
+            &["controls_system", "autopilot_system", "click_system"],
+        );
+        builder.add(
+            RecordingSystem,
+            "recording_system",
+            &["controls_system", "autopilot_system", "route_system"],
+        );
+        builder.add(
+            BallSystem,
+            "ball_system",
+            &["controls_system", "autopilot_system", "route_system"],
+        );
         builder.add(CoordinateSystem, "coordinate_system", &["ball_system"]);
+        builder.add(StatsSystem, "stats_system", &["ball_system"]);
+        builder.add(GoalSystem, "goal_system", &["ball_system"]);
+        builder.add(GhostSystem, "ghost_system", &["ball_system"]);
+        builder.add(RaceSystem, "race_system", &["ball_system", "goal_system"]);
+        builder.add(
+            SpeedrunSystem,
+            "speedrun_system",
+            &["ball_system", "goal_system", "race_system"],
+        );
         builder.add(PathSystem, "path_system", &[]);
+        builder.add(LocaleSystem, "locale_system", &[]);
         builder.add(PathSegmentSystem, "path_segment_system", &[]);
-        builder.add(VideographerSystem, "videographer_system", &[]);
+        builder.add(
+            CollectibleSystem,
+            "collectible_system",
+            &["ball_system", "path_segment_system"],
+        );
+        builder.add(
+            NpcSystem,
+            "npc_system",
+            &["ball_system", "path_segment_system"],
+        );
+        builder.add(CheckpointSystem, "checkpoint_system", &["ball_system"]);
+        builder.add(
+            HazardSystem,
+            "hazard_system",
+            &["ball_system", "checkpoint_system", "path_segment_system"],
+        );
+        builder.add(PortalSystem, "portal_system", &["ball_system"]);
+        builder.add(
+            KeySystem,
+            "key_system",
+            &["ball_system", "path_segment_system"],
+        );
+        builder.add(
+            HudSystem,
+            "hud_system",
+            &[
+                "coordinate_system",
+                "stats_system",
+                "race_system",
+                "speedrun_system",
+                "collectible_system",
+                "key_system",
+            ],
+        );
+        builder.add(
+            VideographerSystem,
+            "videographer_system",
+            &["controls_system", "hazard_system", "portal_system"],
+        );
         Ok(())
     }
 }
@@ -1,7 +1,7 @@
 use crate::config::WanderballConfig;
 use crate::resources::save::CameraRecord;
 use amethyst::{
-    core::{transform::Transform, Parent},
+    core::{math::Vector4, transform::Transform, Parent},
     ecs::{Entity, World},
     prelude::*,
     renderer::Camera,
@@ -50,3 +50,19 @@ pub fn zoom(camera: &mut Camera, width: f32, height: f32) {
     camera.matrix[(1, 3)] = -(top + bottom) / (top - bottom);
     camera.inverse = camera.matrix.try_inverse().expect("Camera projection matrix is not invertible. This is normally due to having inverse values being superimposed (near=far, right=left)")
 }
+
+/// Where a spot on the window is in the world, seen through a camera riding along with its
+/// videographer. Window positions are in pixels from the top left.
+pub fn screen_to_world(
+    camera: &Camera,
+    videographer: &Transform,
+    (x, y): (f32, f32),
+    (width, height): (f32, f32),
+) -> (f32, f32) {
+    let on_camera =
+        camera.inverse * Vector4::new(2.0 * x / width - 1.0, 2.0 * y / height - 1.0, 0.0, 1.0);
+    (
+        videographer.translation().x + on_camera.x,
+        videographer.translation().y + on_camera.y,
+    )
+}
@@ -6,9 +6,15 @@ use amethyst::{
     renderer::{SpriteRender, SpriteSheet},
 };
 
+use crate::components::hazard::LastCheckpoint;
+use crate::components::path::unique_segment_count;
+use crate::components::player::{Player, MAX_PLAYERS};
+use crate::components::portal::PortalCooldown;
 use crate::components::shapes::circle::Circle;
+use crate::components::surface::Momentum;
+use crate::components::wanderdata::{pedometer_from_record, Pedometer};
 use crate::config::WanderballConfig;
-use crate::resources::save::BallRecord;
+use crate::resources::save::{BallRecord, PathSegmentRecord, PedometerRecord};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
@@ -21,14 +27,39 @@ impl Component for Ball {
 pub fn load_ball(
     world: &mut World,
     balls: Vec<BallRecord>,
+    pedometer: &PedometerRecord,
     sprite_sheet_handle: &Handle<SpriteSheet>,
 ) {
-    for ball in balls {
+    let segment_count = unique_segment_count(&world.read_resource::<Vec<PathSegmentRecord>>());
+
+    for (index, ball) in balls.into_iter().enumerate() {
+        // saves from before multiplayer only ever had the one ball, and the one pedometer for it
+        let player = ball.player.unwrap_or_else(|| Player::new(index));
+        let pedometer = match ball.pedometer {
+            Some(record) => pedometer_from_record(record, segment_count),
+            None if player.index == 0 => pedometer_from_record(pedometer.clone(), segment_count),
+            None => Pedometer {
+                segment_count,
+                ..Default::default()
+            },
+        };
+        // saves from before checkpoints send the ball back to wherever it was saved
+        let checkpoint = ball.checkpoint.unwrap_or(LastCheckpoint {
+            x: ball.transform.translation().x,
+            y: ball.transform.translation().y,
+        });
+
         let sprite_render = SpriteRender::new(sprite_sheet_handle.clone(), 0);
         world
             .create_entity()
             .with(sprite_render)
             .with(Ball::default())
+            .with(player)
+            .with(player.tint())
+            .with(pedometer)
+            .with(Momentum::default())
+            .with(checkpoint)
+            .with(PortalCooldown::default())
             .with(ball.circle)
             .with(ball.transform)
             .build();
@@ -36,31 +67,49 @@ pub fn load_ball(
 }
 
 pub fn initialize_ball(world: &mut World, sprite_sheet_handle: &Handle<SpriteSheet>) {
-    let mut local_transform = Transform::default();
-
-    let (ball_radius, start_x, start_y, start_z) = {
+    let (ball_radius, start_x, start_y, start_z, players) = {
         let config = &world.read_resource::<WanderballConfig>();
         (
             config.ball_radius,
             config.start_x,
             config.start_y,
             config.start_ball_z,
+            config.players,
         )
     };
 
-    let x = start_x;
-    let y = start_y;
-    let z = start_z;
+    let segment_count = unique_segment_count(&world.read_resource::<Vec<PathSegmentRecord>>());
+    let players = players.clamp(1, MAX_PLAYERS);
 
-    local_transform.set_translation_xyz(x, y, z);
+    for index in 0..players {
+        let mut local_transform = Transform::default();
 
-    let sprite_render = SpriteRender::new(sprite_sheet_handle.clone(), 0);
+        // everyone lines up side by side across the first segment
+        let offset = (index as f32 - (players - 1) as f32 * 0.5) * ball_radius * 2.5;
+        let x = start_x + offset;
+        let y = start_y;
+        let z = start_z;
+
+        local_transform.set_translation_xyz(x, y, z);
+
+        let sprite_render = SpriteRender::new(sprite_sheet_handle.clone(), 0);
+        let player = Player::new(index);
 
-    world
-        .create_entity()
-        .with(sprite_render)
-        .with(Ball::default())
-        .with(Circle::new(ball_radius))
-        .with(local_transform)
-        .build();
+        world
+            .create_entity()
+            .with(sprite_render)
+            .with(Ball::default())
+            .with(player)
+            .with(player.tint())
+            .with(Pedometer {
+                segment_count,
+                ..Default::default()
+            })
+            .with(Momentum::default())
+            .with(LastCheckpoint { x, y })
+            .with(PortalCooldown::default())
+            .with(Circle::new(ball_radius))
+            .with(local_transform)
+            .build();
+    }
 }
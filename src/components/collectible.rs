@@ -0,0 +1,134 @@
+use amethyst::{
+    assets::Handle,
+    core::{transform::Transform, Hidden},
+    ecs::{Component, VecStorage, World},
+    prelude::*,
+    renderer::{palette::Srgba, resources::Tint, SpriteRender, SpriteSheet},
+};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::components::level::Level;
+use crate::components::path::segment_key;
+use crate::components::shapes::circle::Circle;
+use crate::config::WanderballConfig;
+use crate::resources::save::{CollectibleRecord, PathSegmentRecord};
+use serde::{Deserialize, Serialize};
+
+pub const COLLECTIBLE_SPRITE_INDEX: usize = 2;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum CollectibleKind {
+    Coin,
+    Gem,
+    Star,
+}
+
+impl CollectibleKind {
+    pub fn tint(self) -> Tint {
+        match self {
+            CollectibleKind::Coin => Tint(Srgba::new(1.0, 0.85, 0.2, 1.0)),
+            CollectibleKind::Gem => Tint(Srgba::new(0.3, 0.9, 1.0, 1.0)),
+            CollectibleKind::Star => Tint(Srgba::new(1.0, 0.4, 0.9, 1.0)),
+        }
+    }
+}
+
+/// Something sitting on the path waiting for the ball to roll over it
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Collectible {
+    pub kind: CollectibleKind,
+    pub collected: bool,
+    /// the key of the path segment it was placed on
+    pub segment: String,
+}
+
+impl Component for Collectible {
+    type Storage = VecStorage<Self>;
+}
+
+/// Running tally of what's been picked up, kept up to date by the collectible system
+#[derive(Debug, Clone, Default)]
+pub struct Collection {
+    pub collected: usize,
+    pub total: usize,
+}
+
+pub fn load_collectibles(
+    world: &mut World,
+    collectibles: Vec<CollectibleRecord>,
+    sprite_sheet_handle: &Handle<SpriteSheet>,
+) {
+    let collectible_radius = world.read_resource::<WanderballConfig>().collectible_radius;
+
+    for collectible in collectibles {
+        let sprite_render =
+            SpriteRender::new(sprite_sheet_handle.clone(), COLLECTIBLE_SPRITE_INDEX);
+        world
+            .create_entity()
+            .with(sprite_render)
+            .with(collectible.collectible.kind.tint())
+            .with(Circle::new(collectible_radius))
+            .with(collectible.collectible)
+            .with(collectible.transform)
+            .with(Hidden)
+            .build();
+    }
+}
+
+pub fn initialize_collectibles(world: &mut World, sprite_sheet_handle: &Handle<SpriteSheet>) {
+    let (collectible_chance, collectible_radius, start_z) = {
+        let config = &world.read_resource::<WanderballConfig>();
+        (
+            config.collectible_chance,
+            config.collectible_radius,
+            config.start_path_z,
+        )
+    };
+
+    let path_segments = world.read_resource::<Vec<PathSegmentRecord>>().clone();
+
+    // offset from the path's seed so placement doesn't just mirror the path's turns
+    let seed = world.read_resource::<Level>().seed.wrapping_add(1);
+    let mut rng = StdRng::seed_from_u64(seed);
+    // skip the first segment, no fun finding something where you start
+    for segment in path_segments.iter().skip(1) {
+        if !rng.gen_bool(collectible_chance.into()) {
+            continue;
+        }
+
+        let kind = match rng.gen_range(0, 3) {
+            0 => CollectibleKind::Coin,
+            1 => CollectibleKind::Gem,
+            _ => CollectibleKind::Star,
+        };
+
+        // sits between the path and the ball
+        let mut transform = Transform::default();
+        transform.set_translation_xyz(
+            segment.transform.translation().x,
+            segment.transform.translation().y,
+            start_z + 0.5,
+        );
+
+        let sprite_render =
+            SpriteRender::new(sprite_sheet_handle.clone(), COLLECTIBLE_SPRITE_INDEX);
+        world
+            .create_entity()
+            .with(sprite_render)
+            .with(kind.tint())
+            .with(Circle::new(collectible_radius))
+            .with(Collectible {
+                kind,
+                collected: false,
+                segment: segment_key(&segment.transform),
+            })
+            .with(transform)
+            .with(Hidden)
+            .build();
+    }
+}
+
+pub fn init_collection(world: &mut World) {
+    world.insert(Collection::default());
+}
@@ -0,0 +1,43 @@
+use amethyst::{core::transform::Transform, ecs::World, prelude::*};
+
+use crate::components::path::segment_key;
+use crate::resources::save::{FogOfWarRecord, PathSegmentRecord};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Keeps track of which path segments the ball has come close enough to see
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct FogOfWar {
+    pub revealed: HashMap<String, ()>,
+}
+
+impl FogOfWar {
+    pub fn is_revealed(&self, transform: &Transform) -> bool {
+        self.revealed.contains_key(&segment_key(transform))
+    }
+
+    pub fn reveal(&mut self, transform: &Transform) {
+        self.revealed.insert(segment_key(transform), ());
+    }
+
+    pub fn revealed_count(&self) -> usize {
+        self.revealed.len()
+    }
+}
+
+pub fn load_fog(world: &mut World, fog_record: FogOfWarRecord) {
+    world.insert(FogOfWar {
+        revealed: fog_record.revealed,
+    });
+}
+
+pub fn initialize_fog(world: &mut World) {
+    let mut fog = FogOfWar::default();
+
+    // the ball starts out on the first segment so there's no sense hiding it
+    if let Some(first) = world.read_resource::<Vec<PathSegmentRecord>>().first() {
+        fog.reveal(&first.transform);
+    }
+
+    world.insert(fog);
+}
@@ -0,0 +1,261 @@
+use amethyst::{
+    assets::Handle,
+    core::{transform::Transform, Hidden},
+    ecs::{Component, VecStorage, World},
+    prelude::*,
+    renderer::{palette::Srgba, resources::Tint, SpriteRender, SpriteSheet},
+};
+
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use crate::components::path::segment_key;
+use crate::components::surface::Surface;
+use crate::config::WanderballConfig;
+use crate::resources::locale::Locale;
+use crate::resources::save::{KeyRecord, PathSegmentRecord};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+pub const KEY_SPRITE_INDEX: usize = 4;
+
+/// Which gate a key opens, there's only ever one gate of each colour
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum KeyColor {
+    Red,
+    Blue,
+    Yellow,
+    Green,
+}
+
+impl KeyColor {
+    pub const ALL: [KeyColor; 4] = [
+        KeyColor::Red,
+        KeyColor::Blue,
+        KeyColor::Yellow,
+        KeyColor::Green,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            KeyColor::Red => "red",
+            KeyColor::Blue => "blue",
+            KeyColor::Yellow => "yellow",
+            KeyColor::Green => "green",
+        }
+    }
+
+    pub fn tint(self) -> Tint {
+        match self {
+            KeyColor::Red => Tint(Srgba::new(1.0, 0.3, 0.3, 1.0)),
+            KeyColor::Blue => Tint(Srgba::new(0.3, 0.5, 1.0, 1.0)),
+            KeyColor::Yellow => Tint(Srgba::new(1.0, 0.9, 0.2, 1.0)),
+            KeyColor::Green => Tint(Srgba::new(0.3, 1.0, 0.4, 1.0)),
+        }
+    }
+
+    /// Gates are a darker shade of their key so they read as something in the way
+    pub fn gate_tint(self) -> Tint {
+        let Tint(color) = self.tint();
+        Tint(Srgba::new(
+            color.red * 0.5,
+            color.green * 0.5,
+            color.blue * 0.5,
+            color.alpha,
+        ))
+    }
+}
+
+/// A path segment nobody can roll onto until its key's been picked up
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct Gate {
+    pub color: KeyColor,
+}
+
+impl Component for Gate {
+    type Storage = VecStorage<Self>;
+}
+
+/// Lying somewhere on the path before the gate it opens
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Key {
+    pub color: KeyColor,
+    pub collected: bool,
+    /// the key of the path segment it was placed on
+    pub segment: String,
+}
+
+impl Component for Key {
+    type Storage = VecStorage<Self>;
+}
+
+/// The keys picked up so far, shared by every player
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Inventory {
+    pub keys: Vec<KeyColor>,
+}
+
+impl Inventory {
+    pub fn has(&self, color: KeyColor) -> bool {
+        self.keys.contains(&color)
+    }
+
+    /// Whether a segment's free to roll onto
+    pub fn opens(&self, segment: &PathSegmentRecord) -> bool {
+        segment.gate.map_or(true, |color| self.has(color))
+    }
+
+    pub fn text(&self, locale: &Locale) -> String {
+        if self.keys.is_empty() {
+            locale.text("hud.keys_none")
+        } else {
+            let names: Vec<String> = self
+                .keys
+                .iter()
+                .map(|color| locale.text(&format!("hud.key_{}", color.name())))
+                .collect();
+            locale.format("hud.keys", &[&names.join(", ")])
+        }
+    }
+}
+
+/// Locks some segments and puts the key for each somewhere earlier along the path. The path's
+/// laid down one touching segment after another, so everything before a gate can be reached
+/// without going through it, and every gate before that has its own key even further back.
+pub fn place_gates(
+    config: &WanderballConfig,
+    seed: u64,
+    path_segments: &mut [PathSegmentRecord],
+) -> Vec<KeyRecord> {
+    let last = path_segments.len().saturating_sub(1);
+    let mut laid_down: HashMap<String, usize> = HashMap::new();
+    for segment in path_segments.iter() {
+        *laid_down
+            .entry(segment_key(&segment.transform))
+            .or_insert(0) += 1;
+    }
+    // segments with others laid on top could be rolled over anyway, so they make poor gates
+    // and poor places to hide a key
+    let single = |segment: &PathSegmentRecord| laid_down[&segment_key(&segment.transform)] == 1;
+
+    // the first one's left for the ball and the second for the earliest key
+    let mut candidates: Vec<usize> = (2..path_segments.len())
+        .filter(|i| {
+            let segment = &path_segments[*i];
+            !(config.goal_mode && *i == last)
+                && !segment.checkpoint
+                && segment.surface == Surface::Plain
+                && single(segment)
+        })
+        .collect();
+
+    // offset from the path's seed, and everything else placed on it
+    let mut rng = StdRng::seed_from_u64(seed.wrapping_add(6));
+    candidates.shuffle(&mut rng);
+    let mut gates: Vec<usize> = candidates
+        .into_iter()
+        .take(config.gates.min(KeyColor::ALL.len()))
+        .collect();
+    gates.sort_unstable();
+
+    let mut keys = vec![];
+    for (color, gate) in KeyColor::ALL.iter().copied().zip(gates.iter().copied()) {
+        let spots: Vec<usize> = (1..gate)
+            .filter(|i| !gates.contains(i) && single(&path_segments[*i]))
+            .collect();
+        let spot = match spots.choose(&mut rng) {
+            Some(spot) => *spot,
+            None => continue,
+        };
+
+        let segment = &mut path_segments[gate];
+        segment.gate = Some(color);
+        segment.tint = color.gate_tint();
+
+        // sits between the path and the ball, like collectibles do
+        let mut transform = Transform::default();
+        transform.set_translation_xyz(
+            path_segments[spot].transform.translation().x,
+            path_segments[spot].transform.translation().y,
+            config.start_path_z + 0.5,
+        );
+        keys.push(KeyRecord {
+            transform,
+            key: Key {
+                color,
+                collected: false,
+                segment: segment_key(&path_segments[spot].transform),
+            },
+        });
+    }
+
+    keys
+}
+
+pub fn load_keys(
+    world: &mut World,
+    keys: Vec<KeyRecord>,
+    sprite_sheet_handle: &Handle<SpriteSheet>,
+) {
+    for key in keys {
+        let sprite_render = SpriteRender::new(sprite_sheet_handle.clone(), KEY_SPRITE_INDEX);
+        world
+            .create_entity()
+            .with(sprite_render)
+            .with(key.key.color.tint())
+            .with(key.key)
+            .with(key.transform)
+            .with(Hidden)
+            .build();
+    }
+}
+
+pub fn load_inventory(world: &mut World, inventory: Inventory) {
+    world.insert(inventory);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::path::generate_path;
+
+    fn gates_and_keys(seed: u64) -> (Vec<Option<KeyColor>>, Vec<(KeyColor, String)>) {
+        let config = WanderballConfig::default();
+        let mut path_segments = generate_path(&config, seed, 200);
+        let keys = place_gates(&config, seed, &mut path_segments);
+
+        (
+            path_segments.iter().map(|segment| segment.gate).collect(),
+            keys.into_iter()
+                .map(|record| (record.key.color, record.key.segment))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn the_same_seed_places_the_same_gates_and_keys() {
+        let (gates, keys) = gates_and_keys(11);
+
+        assert!(gates.iter().any(|gate| gate.is_some()));
+        assert_eq!((gates, keys), gates_and_keys(11));
+    }
+
+    #[test]
+    fn every_key_comes_before_its_gate() {
+        let config = WanderballConfig::default();
+        let mut path_segments = generate_path(&config, 11, 200);
+        let keys = place_gates(&config, 11, &mut path_segments);
+
+        assert!(!keys.is_empty());
+        for record in keys {
+            let gate = path_segments
+                .iter()
+                .position(|segment| segment.gate == Some(record.key.color))
+                .expect("a key without a gate");
+            let key = path_segments
+                .iter()
+                .position(|segment| segment_key(&segment.transform) == record.key.segment)
+                .expect("a key off the path");
+            assert!(key < gate);
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use amethyst::{
+    assets::Handle,
+    core::transform::Transform,
+    ecs::{Component, VecStorage, World},
+    prelude::*,
+    renderer::{
+        palette::Srgba, resources::Tint, transparent::Transparent, SpriteRender, SpriteSheet,
+    },
+};
+
+use crate::components::level::Level;
+use crate::config::WanderballConfig;
+use crate::resources::replay::named_replay;
+
+/// A see-through ball retracing a recorded run. It isn't a `Ball` so nothing it does counts
+#[derive(Debug, Clone, Default)]
+pub struct Ghost {
+    pub positions: Vec<[f32; 2]>,
+    pub frame: usize,
+}
+
+impl Component for Ghost {
+    type Storage = VecStorage<Self>;
+}
+
+/// Spawns a ghost if the config picks a replay that was recorded on the level we're playing
+pub fn initialize_ghost(world: &mut World, sprite_sheet_handle: &Handle<SpriteSheet>) {
+    let (ghost_replay, start_x, start_y, start_z) = {
+        let config = &world.read_resource::<WanderballConfig>();
+        (
+            config.ghost_replay.clone(),
+            config.start_x,
+            config.start_y,
+            config.start_ball_z,
+        )
+    };
+
+    let replay = match ghost_replay.as_deref().and_then(named_replay) {
+        Some(replay) => replay,
+        None => return,
+    };
+
+    if replay.seed != world.read_resource::<Level>().seed || replay.positions.is_empty() {
+        log::info!("ghost replay wasn't recorded on this level, no ghost this time");
+        return;
+    }
+
+    // tucked just underneath the ball
+    let mut local_transform = Transform::default();
+    local_transform.set_translation_xyz(start_x, start_y, start_z - 0.25);
+
+    let sprite_render = SpriteRender::new(sprite_sheet_handle.clone(), 0);
+
+    world
+        .create_entity()
+        .with(sprite_render)
+        .with(Tint(Srgba::new(1.0, 1.0, 1.0, 0.35)))
+        .with(Transparent)
+        .with(Ghost {
+            positions: replay.positions,
+            frame: 0,
+        })
+        .with(local_transform)
+        .build();
+}
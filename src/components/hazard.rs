@@ -0,0 +1,199 @@
+use amethyst::{
+    assets::Handle,
+    core::{math::Vector3, transform::Transform, Hidden},
+    ecs::{Component, NullStorage, VecStorage, World},
+    prelude::*,
+    renderer::{palette::Srgba, resources::Tint, SpriteRender, SpriteSheet},
+};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::components::level::Level;
+use crate::components::path::segment_key;
+use crate::components::portal::Portals;
+use crate::components::shapes::rectangle::Rectangle;
+use crate::config::WanderballConfig;
+use crate::resources::save::{HazardRecord, PathSegmentRecord};
+use serde::{Deserialize, Serialize};
+
+pub const HAZARD_SPRITE_INDEX: usize = 3;
+/// how big the hazard sprite is on the sheet, it gets scaled to the configured size
+const HAZARD_SPRITE_SIZE: f32 = 6.0;
+
+/// Where a hazard slides between, it goes from one end to the other and back again
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Patrol {
+    pub from: [f32; 2],
+    pub to: [f32; 2],
+    /// units a second
+    pub speed: f32,
+}
+
+impl Patrol {
+    pub fn length(&self) -> f32 {
+        ((self.to[0] - self.from[0]).powi(2) + (self.to[1] - self.from[1]).powi(2)).sqrt()
+    }
+
+    /// Where along the patrol a hazard is for a phase, 0 to 1 is on the way out and 1 to 2 is
+    /// on the way back
+    pub fn position(&self, phase: f32) -> (f32, f32) {
+        let t = if phase <= 1.0 { phase } else { 2.0 - phase };
+        (
+            self.from[0] + (self.to[0] - self.from[0]) * t,
+            self.from[1] + (self.to[1] - self.from[1]) * t,
+        )
+    }
+}
+
+/// Something sliding up and down a path segment, rolling into it sends the ball back to the
+/// last checkpoint it reached
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Hazard {
+    pub patrol: Patrol,
+    /// how far round its patrol it is, from 0 up to 2
+    pub phase: f32,
+    /// the key of the path segment it patrols
+    pub segment: String,
+}
+
+impl Component for Hazard {
+    type Storage = VecStorage<Self>;
+}
+
+impl Hazard {
+    pub fn tint() -> Tint {
+        Tint(Srgba::new(1.0, 0.25, 0.2, 1.0))
+    }
+
+    /// Moves the hazard along its patrol, turning round at either end
+    pub fn advance(&mut self, delta_seconds: f32) {
+        let length = self.patrol.length();
+        if length <= f32::EPSILON {
+            return;
+        }
+        self.phase = (self.phase + self.patrol.speed * delta_seconds / length) % 2.0;
+    }
+}
+
+/// Marks a path segment as somewhere safe to be sent back to
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default)]
+pub struct Checkpoint;
+
+impl Component for Checkpoint {
+    type Storage = NullStorage<Self>;
+}
+
+impl Checkpoint {
+    pub fn tint() -> Tint {
+        Tint(Srgba::new(0.4, 1.0, 0.9, 1.0))
+    }
+}
+
+/// Where a player's ball goes back to when it hits a hazard
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct LastCheckpoint {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Component for LastCheckpoint {
+    type Storage = VecStorage<Self>;
+}
+
+pub fn load_hazards(
+    world: &mut World,
+    hazards: Vec<HazardRecord>,
+    sprite_sheet_handle: &Handle<SpriteSheet>,
+) {
+    for hazard in hazards {
+        let sprite_render = SpriteRender::new(sprite_sheet_handle.clone(), HAZARD_SPRITE_INDEX);
+        world
+            .create_entity()
+            .with(sprite_render)
+            .with(Hazard::tint())
+            .with(hazard.rectangle)
+            .with(hazard.hazard)
+            .with(hazard.transform)
+            .with(Hidden)
+            .build();
+    }
+}
+
+pub fn initialize_hazards(world: &mut World, sprite_sheet_handle: &Handle<SpriteSheet>) {
+    let (hazard_chance, hazard_speed, hazard_size, start_z) = {
+        let config = &world.read_resource::<WanderballConfig>();
+        (
+            config.hazard_chance,
+            config.hazard_speed,
+            config.hazard_size,
+            config.start_path_z,
+        )
+    };
+
+    let path_segments = world.read_resource::<Vec<PathSegmentRecord>>().clone();
+    let goal = world.read_resource::<Level>().goal.clone();
+    let portals = world.read_resource::<Portals>().clone();
+
+    // offset from the path's seed, and everything else placed on it
+    let seed = world.read_resource::<Level>().seed.wrapping_add(4);
+    let mut rng = StdRng::seed_from_u64(seed);
+    // never where the ball starts, and never anywhere it's meant to be safe
+    for segment in path_segments.iter().skip(1) {
+        if !rng.gen_bool(hazard_chance.into()) {
+            continue;
+        }
+
+        let key = segment_key(&segment.transform);
+        if segment.checkpoint || portals.contains(&key) || goal.as_ref() == Some(&key) {
+            continue;
+        }
+
+        let x = segment.transform.translation().x;
+        let y = segment.transform.translation().y;
+        let half_width = segment.rectangle.width * 0.5;
+        let half_height = segment.rectangle.height * 0.5;
+        let half_size = hazard_size * 0.5;
+        // hugs one side of the segment so there's room to squeeze past on the other
+        let side = if rng.gen_bool(0.5) { 1.0 } else { -1.0 };
+
+        let patrol = if segment.rectangle.width >= segment.rectangle.height {
+            let across = y + side * (half_height - half_size);
+            Patrol {
+                from: [x - half_width + half_size, across],
+                to: [x + half_width - half_size, across],
+                speed: hazard_speed,
+            }
+        } else {
+            let across = x + side * (half_width - half_size);
+            Patrol {
+                from: [across, y - half_height + half_size],
+                to: [across, y + half_height - half_size],
+                speed: hazard_speed,
+            }
+        };
+
+        let hazard = Hazard {
+            patrol,
+            phase: rng.gen_range(0.0, 2.0),
+            segment: key,
+        };
+
+        // above the path and collectibles, below the balls
+        let (hazard_x, hazard_y) = patrol.position(hazard.phase);
+        let mut transform = Transform::default();
+        transform.set_translation_xyz(hazard_x, hazard_y, start_z + 0.6);
+        let scale = hazard_size / HAZARD_SPRITE_SIZE;
+        transform.set_scale(Vector3::new(scale, scale, 1.0));
+
+        let sprite_render = SpriteRender::new(sprite_sheet_handle.clone(), HAZARD_SPRITE_INDEX);
+        world
+            .create_entity()
+            .with(sprite_render)
+            .with(Hazard::tint())
+            .with(Rectangle::new(hazard_size, hazard_size))
+            .with(hazard)
+            .with(transform)
+            .with(Hidden)
+            .build();
+    }
+}
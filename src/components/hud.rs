@@ -0,0 +1,99 @@
+use amethyst::{
+    assets::Loader,
+    ecs::{Component, VecStorage, World},
+    prelude::*,
+    ui::{FontHandle, LineMode, TtfFormat, UiText, UiTransform},
+};
+
+use crate::components::player::Player;
+use crate::config::HudLayout;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Where a HUD widget gets its text from
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum HudSource {
+    /// where the first player's ball is
+    Coordinates,
+    /// new segments rolled onto by everyone
+    Steps,
+    /// the full line of pedometer stats
+    Stats,
+    /// how much of the path has been visited
+    Exploration,
+    /// collectibles picked up out of the ones on the path
+    Collection,
+    /// the speedrun clock, only in timer mode
+    Timer,
+    /// keys picked up, only on levels with gates
+    Inventory,
+    /// one player's own steps, only with more than one player
+    PlayerSteps(usize),
+    Fps,
+}
+
+/// A line of text on the HUD, kept up to date by the HUD system
+#[derive(Debug, Clone, Copy)]
+pub struct HudWidget {
+    pub source: HudSource,
+}
+
+impl Component for HudWidget {
+    type Storage = VecStorage<Self>;
+}
+
+/// Puts every widget in the HUD layout on screen, they all start out empty until the HUD system
+/// fills them in
+pub fn init_hud(world: &mut World) {
+    let layout = world.read_resource::<HudLayout>().clone();
+    let mut fonts: HashMap<String, FontHandle> = HashMap::new();
+
+    for widget in layout.widgets {
+        let path = widget.font.unwrap_or_else(|| layout.font.clone());
+        let font = fonts
+            .entry(path)
+            .or_insert_with_key(|path| {
+                world.read_resource::<Loader>().load(
+                    path.as_str(),
+                    TtfFormat,
+                    (),
+                    &world.read_resource(),
+                )
+            })
+            .clone();
+
+        // each player's steps are in their ball's color unless the layout says otherwise
+        let color = match (widget.color, widget.source) {
+            (Some(color), _) => color,
+            (None, HudSource::PlayerSteps(index)) => Player::new(index).color(),
+            (None, _) => layout.color,
+        };
+
+        let transform = UiTransform::new(
+            widget.id,
+            widget.anchor,
+            widget.anchor,
+            widget.x,
+            widget.y,
+            layout.z,
+            widget.width,
+            widget.height,
+        );
+
+        world
+            .create_entity()
+            .with(transform)
+            .with(UiText::new(
+                font,
+                String::new(),
+                color,
+                widget.font_size.unwrap_or(layout.font_size),
+                LineMode::Single,
+                widget.align,
+            ))
+            .with(HudWidget {
+                source: widget.source,
+            })
+            .build();
+    }
+}
@@ -0,0 +1,61 @@
+use amethyst::{ecs::World, prelude::*};
+
+use crate::config::WanderballConfig;
+use crate::resources::save::LevelRecord;
+use serde::{Deserialize, Serialize};
+
+/// Which level we're on and everything needed to generate its path again
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Level {
+    pub number: u32,
+    pub seed: u64,
+    pub path_length: usize,
+    /// the key of the segment that ends the level, only set in goal mode
+    pub goal: Option<String>,
+    pub complete: bool,
+}
+
+impl Level {
+    pub fn first(config: &WanderballConfig) -> Level {
+        Level {
+            number: 1,
+            seed: rand::random(),
+            path_length: config.path_length,
+            goal: None,
+            complete: false,
+        }
+    }
+
+    /// A brand new path to wander, a little longer than this one
+    pub fn next(&self, config: &WanderballConfig) -> Level {
+        Level {
+            number: self.number + 1,
+            seed: rand::random(),
+            path_length: self.path_length + config.path_length_increment,
+            goal: None,
+            complete: false,
+        }
+    }
+}
+
+pub fn load_level(world: &mut World, level_record: Option<LevelRecord>) {
+    let level = match level_record {
+        Some(record) => Level {
+            number: record.number,
+            seed: record.seed,
+            path_length: record.path_length,
+            goal: record.goal,
+            complete: false,
+        },
+        // saves from before there were levels
+        None => Level::first(&world.read_resource::<WanderballConfig>()),
+    };
+
+    world.insert(level);
+}
+
+pub fn initialize_level(world: &mut World, level: Option<Level>) {
+    let level = level.unwrap_or_else(|| Level::first(&world.read_resource::<WanderballConfig>()));
+
+    world.insert(level);
+}
@@ -0,0 +1,15 @@
+use amethyst::ecs::{Component, DenseVecStorage};
+
+/// Ui text that came from a string key, so it can be looked up again when the language changes
+#[derive(Debug, Clone)]
+pub struct Localized {
+    pub key: String,
+    /// the locale's revision it was last looked up in
+    pub revision: u32,
+    /// what it was set to then, text that's been changed since is left alone
+    pub text: String,
+}
+
+impl Component for Localized {
+    type Storage = DenseVecStorage<Self>;
+}
@@ -1,5 +1,18 @@
 pub mod ball;
+pub mod collectible;
+pub mod fog;
+pub mod gate;
+pub mod ghost;
+pub mod hazard;
+pub mod hud;
+pub mod level;
+pub mod localized;
+pub mod npc;
 pub mod path;
+pub mod player;
+pub mod portal;
 pub mod shapes;
+pub mod speedrun;
+pub mod surface;
 pub mod videographer;
 pub mod wanderdata;
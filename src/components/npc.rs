@@ -0,0 +1,160 @@
+use amethyst::{
+    assets::Handle,
+    core::{transform::Transform, Hidden},
+    ecs::{Component, VecStorage, World},
+    prelude::*,
+    renderer::{palette::Srgba, resources::Tint, SpriteRender, SpriteSheet},
+};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::components::ball::Ball;
+use crate::components::level::Level;
+use crate::components::path::segment_key;
+use crate::components::portal::Portals;
+use crate::components::shapes::circle::Circle;
+use crate::config::WanderballConfig;
+use crate::resources::graph::SegmentGraph;
+use crate::resources::save::{NpcRecord, PathSegmentRecord};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// How an NPC ball decides where to wander next
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum Behavior {
+    /// hops to a random neighbouring segment, trying not to double back
+    RandomWalk,
+    /// heads for the closest segment it hasn't been to yet
+    SeekUnexplored,
+    /// tags along behind the first player
+    FollowPlayer,
+}
+
+impl Behavior {
+    pub fn tint(self) -> Tint {
+        match self {
+            Behavior::RandomWalk => Tint(Srgba::new(0.6, 0.6, 0.6, 1.0)),
+            Behavior::SeekUnexplored => Tint(Srgba::new(0.8, 0.6, 1.0, 1.0)),
+            Behavior::FollowPlayer => Tint(Srgba::new(1.0, 0.8, 0.4, 1.0)),
+        }
+    }
+}
+
+/// A ball nobody's driving, it finds its own way around the path
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Npc {
+    pub behavior: Behavior,
+    /// segment keys still to roll through, the next one first
+    pub route: Vec<String>,
+    /// where the route ends up, following NPCs replan when the player moves on from it
+    pub destination: Option<String>,
+    pub visited: HashMap<String, ()>,
+    pub previous: Option<String>,
+}
+
+impl Component for Npc {
+    type Storage = VecStorage<Self>;
+}
+
+impl Npc {
+    pub fn new(behavior: Behavior) -> Npc {
+        Npc {
+            behavior,
+            route: vec![],
+            destination: None,
+            visited: HashMap::new(),
+            previous: None,
+        }
+    }
+}
+
+/// Where NPCs' random choices come from, seeded from the path so a replay sees them wander the
+/// same way every time
+pub struct NpcRng(pub StdRng);
+
+impl Default for NpcRng {
+    fn default() -> Self {
+        NpcRng(StdRng::seed_from_u64(0))
+    }
+}
+
+fn seed_npc_rng(world: &mut World) {
+    // offset from the path's seed, and from everything placed on it
+    let seed = world.read_resource::<Level>().seed.wrapping_add(7);
+    world.insert(NpcRng(StdRng::seed_from_u64(seed)));
+}
+
+/// Where NPCs find their way around, built fresh from whatever path and portals are loaded
+pub fn init_segment_graph(world: &mut World) {
+    let mut graph = SegmentGraph::from_segments(&world.read_resource::<Vec<PathSegmentRecord>>());
+    for pair in &world.read_resource::<Portals>().pairs {
+        graph.add_portal(&pair.a.segment, &pair.b.segment);
+    }
+    world.insert(graph);
+}
+
+pub fn load_npcs(
+    world: &mut World,
+    npcs: Vec<NpcRecord>,
+    sprite_sheet_handle: &Handle<SpriteSheet>,
+) {
+    seed_npc_rng(world);
+
+    for npc in npcs {
+        let sprite_render = SpriteRender::new(sprite_sheet_handle.clone(), 0);
+        world
+            .create_entity()
+            .with(sprite_render)
+            .with(Ball::default())
+            .with(npc.npc.behavior.tint())
+            .with(npc.npc)
+            .with(npc.circle)
+            .with(npc.transform)
+            .with(Hidden)
+            .build();
+    }
+}
+
+pub fn initialize_npcs(world: &mut World, sprite_sheet_handle: &Handle<SpriteSheet>) {
+    seed_npc_rng(world);
+
+    let (npcs, ball_radius, start_z) = {
+        let config = &world.read_resource::<WanderballConfig>();
+        (config.npcs.clone(), config.ball_radius, config.start_ball_z)
+    };
+
+    let path_segments = world.read_resource::<Vec<PathSegmentRecord>>().clone();
+    if path_segments.is_empty() {
+        return;
+    }
+
+    // offset from the path's seed, and from the collectibles', so they land somewhere else
+    let seed = world.read_resource::<Level>().seed.wrapping_add(2);
+    let mut rng = StdRng::seed_from_u64(seed);
+    for behavior in npcs {
+        let segment = &path_segments[rng.gen_range(0, path_segments.len())];
+
+        // just under the players so they roll over the top of NPCs
+        let mut transform = Transform::default();
+        transform.set_translation_xyz(
+            segment.transform.translation().x,
+            segment.transform.translation().y,
+            start_z - 0.1,
+        );
+
+        let mut npc = Npc::new(behavior);
+        npc.visited.insert(segment_key(&segment.transform), ());
+
+        let sprite_render = SpriteRender::new(sprite_sheet_handle.clone(), 0);
+        world
+            .create_entity()
+            .with(sprite_render)
+            .with(Ball::default())
+            .with(behavior.tint())
+            .with(npc)
+            .with(Circle::new(ball_radius))
+            .with(transform)
+            .with(Hidden)
+            .build();
+    }
+}
@@ -6,12 +6,18 @@ use amethyst::{
     renderer::{palette::Srgba, resources::Tint, SpriteRender, SpriteSheet},
 };
 
-use rand::Rng;
+use rand::{rngs::StdRng, Rng, SeedableRng};
 
+use crate::components::gate::{place_gates, Gate};
+use crate::components::hazard::Checkpoint;
+use crate::components::level::Level;
+use crate::components::portal::{place_portals, Portal, Portals};
 use crate::components::shapes::rectangle::Rectangle;
+use crate::components::surface::Surface;
 use crate::config::WanderballConfig;
-use crate::resources::save::PathSegmentRecord;
+use crate::resources::save::{KeyRecord, PathSegmentRecord};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct PathSegment;
@@ -27,6 +33,22 @@ impl Component for Path {
     type Storage = VecStorage<Self>;
 }
 
+/// The key a path segment is tracked by in the pedometer and fog of war, derived from where the
+/// segment sits in the world
+pub fn segment_key(transform: &Transform) -> String {
+    format!("{}{}", transform.translation().x, transform.translation().y)
+}
+
+/// How many distinct segments make up the path, segments the generator laid down on top of one
+/// another only count once
+pub fn unique_segment_count(path_segments: &[PathSegmentRecord]) -> usize {
+    path_segments
+        .iter()
+        .map(|segment| segment_key(&segment.transform))
+        .collect::<HashSet<String>>()
+        .len()
+}
+
 const UP: u8 = 0;
 const LEFT: u8 = 1;
 const DOWN: u8 = 2;
@@ -38,65 +60,146 @@ pub fn load_path(
     sprite_sheet_handle: &Handle<SpriteSheet>,
 ) {
     for segment in &path_segments {
+        let portal = world
+            .read_resource::<Portals>()
+            .contains(&segment_key(&segment.transform));
         let segment_render = SpriteRender::new(sprite_sheet_handle.clone(), 1);
-        world
+        let mut builder = world
             .create_entity()
             .with(segment_render)
             .with(PathSegment)
             .with(segment.rectangle.clone())
             .with(segment.tint)
-            .with(segment.transform.clone())
-            .build();
+            .with(segment.surface)
+            .with(segment.transform.clone());
+
+        if segment.checkpoint {
+            builder = builder.with(Checkpoint);
+        }
+
+        if portal {
+            builder = builder.with(Portal);
+        }
+
+        if let Some(color) = segment.gate {
+            builder = builder.with(Gate { color });
+        }
+
+        builder.build();
     }
     world.insert(path_segments)
 }
 
-pub fn initialize_path(world: &mut World, sprite_sheet_handle: &Handle<SpriteSheet>) {
-    let (path_segment_height, path_segment_width, path_length, start_x, start_y, start_z) = {
+/// Lays out a new path, handing back the keys to any gates on it so they can be put out too
+pub fn initialize_path(
+    world: &mut World,
+    sprite_sheet_handle: &Handle<SpriteSheet>,
+) -> Vec<KeyRecord> {
+    let (seed, path_length) = {
+        let level = &world.read_resource::<Level>();
+        (level.seed, level.path_length)
+    };
+
+    let (path_segments, keys, portals, goal) = {
         let config = &world.read_resource::<WanderballConfig>();
+        let mut path_segments = generate_path(config, seed, path_length);
+        let keys = place_gates(config, seed, &mut path_segments);
+        let portals = place_portals(config, seed, &mut path_segments);
         (
-            config.path_segment_height,
-            config.path_segment_width,
-            config.path_length,
-            config.start_x,
-            config.start_y,
-            config.start_path_z,
+            path_segments,
+            keys,
+            Portals { pairs: portals },
+            config.goal_mode,
         )
     };
 
+    // in goal mode the very end of the path is the way out
+    if goal && path_segments.len() > 1 {
+        if let Some(last) = path_segments.last() {
+            world.write_resource::<Level>().goal = Some(segment_key(&last.transform));
+        }
+    }
+
+    let segment_render = SpriteRender::new(sprite_sheet_handle.clone(), 1);
+    for (i, segment) in path_segments.iter().enumerate() {
+        let mut builder = world
+            .create_entity()
+            .with(segment_render.clone())
+            .with(PathSegment)
+            .with(segment.rectangle.clone())
+            .with(segment.transform.clone())
+            .with(segment.tint)
+            .with(segment.surface);
+
+        // the ball starts out on the origin segment so that's the only one we can see
+        if i > 0 {
+            builder = builder.with(Hidden);
+        }
+
+        if segment.checkpoint {
+            builder = builder.with(Checkpoint);
+        }
+
+        if portals.contains(&segment_key(&segment.transform)) {
+            builder = builder.with(Portal);
+        }
+
+        if let Some(color) = segment.gate {
+            builder = builder.with(Gate { color });
+        }
+
+        builder.build();
+    }
+
+    world.insert(path_segments);
+    world.insert(portals);
+
+    keys
+}
+
+/// Lays out a whole path without touching the world, the same seed always wanders the same way
+pub fn generate_path(
+    config: &WanderballConfig,
+    seed: u64,
+    path_length: usize,
+) -> Vec<PathSegmentRecord> {
+    let path_segment_height = config.path_segment_height;
+    let path_segment_width = config.path_segment_width;
+
     let mut path_segments: Vec<PathSegmentRecord> = vec![];
 
     let tint = Tint(Srgba::new(1.0, 1.0, 1.0, 1.0)); // white == no tint
+    let goal_tint = Tint(Srgba::new(0.2, 1.0, 0.4, 1.0));
 
     // origin path segment
-    let mut x = start_x;
-    let mut y = start_y;
-    let z = start_z;
-
-    let segment_render = SpriteRender::new(sprite_sheet_handle.clone(), 1);
+    let mut x = config.start_x;
+    let mut y = config.start_y;
+    let z = config.start_path_z;
 
     let mut first_transform = Transform::default();
     first_transform.set_translation_xyz(x, y, z);
-    let rectangle = Rectangle::new(path_segment_width, path_segment_height);
-    world
-        .create_entity()
-        .with(segment_render.clone())
-        .with(PathSegment)
-        .with(rectangle.clone())
-        .with(first_transform.clone())
-        .with(tint)
-        .build();
-
     path_segments.push(PathSegmentRecord {
         transform: first_transform,
-        rectangle,
+        rectangle: Rectangle::new(path_segment_width, path_segment_height),
         tint,
+        surface: Surface::Plain,
+        checkpoint: false,
+        gate: None,
     });
 
     // Rest of path
-    let mut rng = rand::thread_rng();
+    let mut rng = StdRng::seed_from_u64(seed);
+    // surfaces get their own so the path wanders the same way with or without them
+    let mut surface_rng = StdRng::seed_from_u64(seed.wrapping_add(3));
     let mut last_choice = LEFT;
-    for _ in 1..path_length {
+    // a goal right next to the start would be over before it began, so in goal mode the path
+    // keeps going until its end is far enough out, up to twice as long
+    let near_start = |x: f32, y: f32| {
+        (x - config.start_x).powi(2) + (y - config.start_y).powi(2)
+            < config.goal_min_distance * config.goal_min_distance
+    };
+    let mut i = 1;
+    while i < path_length || (config.goal_mode && i < path_length * 2 && near_start(x, y)) {
         let choice = rng.gen_range(0, 4);
         let mut rotation = 0.0f32;
         match choice {
@@ -180,31 +283,49 @@ pub fn initialize_path(world: &mut World, sprite_sheet_handle: &Handle<SpriteShe
         next_transform.set_translation_xyz(x, y, z);
         next_transform.rotate_2d(rotation.to_radians());
 
-        let rectangle: Rectangle;
-        if choice == LEFT || choice == RIGHT {
-            rectangle = Rectangle::new(path_segment_width, path_segment_height);
+        let rectangle = if choice == LEFT || choice == RIGHT {
+            Rectangle::new(path_segment_width, path_segment_height)
         } else {
             // rotated so we flip width/height to make things easier when we're figuring out how to
             // keep the ball on the path when we want to
-            rectangle = Rectangle::new(path_segment_height, path_segment_width);
-        }
+            Rectangle::new(path_segment_height, path_segment_width)
+        };
 
-        world
-            .create_entity()
-            .with(segment_render.clone())
-            .with(PathSegment)
-            .with(rectangle.clone())
-            .with(next_transform.clone())
-            .with(tint)
-            .with(Hidden)
-            .build();
+        let checkpoint = config.checkpoint_interval > 0 && i % config.checkpoint_interval == 0;
+        let surface = if surface_rng.gen_bool(config.surface_chance.into()) {
+            Surface::random(&mut surface_rng, choice == LEFT || choice == RIGHT)
+        } else {
+            Surface::Plain
+        };
+        // somewhere safe to be sent back to shouldn't be slippery, still rolled for above so the
+        // rest of the surfaces land in the same places
+        let surface = if checkpoint { Surface::Plain } else { surface };
+
+        let tint = if checkpoint {
+            Checkpoint::tint()
+        } else {
+            surface.tint()
+        };
 
         path_segments.push(PathSegmentRecord {
             transform: next_transform,
             rectangle,
             tint,
+            surface,
+            checkpoint,
+            gate: None,
         });
+        i += 1;
     }
 
-    world.insert(path_segments);
+    // in goal mode the very end of the path is the way out, plain so it's easy to see
+    if config.goal_mode && path_segments.len() > 1 {
+        if let Some(goal) = path_segments.last_mut() {
+            goal.tint = goal_tint;
+            goal.surface = Surface::Plain;
+            goal.checkpoint = false;
+        }
+    }
+
+    path_segments
 }
@@ -1,12 +1,50 @@
 use amethyst::{
     ecs::{Component, VecStorage},
+    renderer::{palette::Srgba, resources::Tint},
 };
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug, Clone, Default)]
-pub struct Player;
+pub const MAX_PLAYERS: usize = 4;
+
+const PLAYER_COLORS: [[f32; 4]; MAX_PLAYERS] = [
+    [1.0, 1.0, 1.0, 1.0],
+    [1.0, 0.45, 0.45, 1.0],
+    [0.45, 0.7, 1.0, 1.0],
+    [0.5, 1.0, 0.5, 1.0],
+];
+
+/// Marks a ball as being driven by one of the local players
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+pub struct Player {
+    pub index: usize,
+}
 
 impl Component for Player {
     type Storage = VecStorage<Self>;
-}
\ No newline at end of file
+}
+
+impl Player {
+    pub fn new(index: usize) -> Player {
+        Player { index }
+    }
+
+    pub fn name(self) -> String {
+        format!("p{}", self.index + 1)
+    }
+
+    pub fn color(self) -> [f32; 4] {
+        PLAYER_COLORS[self.index % MAX_PLAYERS]
+    }
+
+    pub fn tint(self) -> Tint {
+        let [r, g, b, a] = self.color();
+        Tint(Srgba::new(r, g, b, a))
+    }
+}
+
+/// Who's winning the race to explore the path, only matters with more than one player
+#[derive(Debug, Clone, Default)]
+pub struct Race {
+    pub winner: Option<Player>,
+}
@@ -0,0 +1,247 @@
+use amethyst::{
+    ecs::{Component, NullStorage, VecStorage, World},
+    renderer::{palette::Srgba, resources::Tint},
+};
+
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use crate::components::path::segment_key;
+use crate::components::surface::Surface;
+use crate::config::WanderballConfig;
+use crate::resources::save::PathSegmentRecord;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Each pair of portals gets its own colour so it's clear which ones go together
+const PORTAL_COLORS: [[f32; 3]; 4] = [
+    [1.0, 0.5, 0.1],
+    [0.3, 0.5, 1.0],
+    [1.0, 0.3, 0.7],
+    [0.6, 1.0, 0.2],
+];
+
+/// One end of a portal, the segment it sits on and where the ball comes out
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PortalEnd {
+    pub segment: String,
+    pub x: f32,
+    pub y: f32,
+}
+
+/// Two far apart segments of the path, rolling onto either one comes out at the other
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PortalPair {
+    pub a: PortalEnd,
+    pub b: PortalEnd,
+}
+
+impl PortalPair {
+    pub fn tint(index: usize) -> Tint {
+        let [red, green, blue] = PORTAL_COLORS[index % PORTAL_COLORS.len()];
+        Tint(Srgba::new(red, green, blue, 1.0))
+    }
+}
+
+/// Every portal on the path
+#[derive(Debug, Clone, Default)]
+pub struct Portals {
+    pub pairs: Vec<PortalPair>,
+}
+
+impl Portals {
+    pub fn contains(&self, segment: &str) -> bool {
+        self.pairs
+            .iter()
+            .any(|pair| pair.a.segment == segment || pair.b.segment == segment)
+    }
+
+    /// Where a ball at a point comes out, if it's close enough to the middle of a portal to go in
+    pub fn exit_from(&self, x: f32, y: f32, radius: f32) -> Option<&PortalEnd> {
+        let within = |end: &PortalEnd| (end.x - x).powi(2) + (end.y - y).powi(2) <= radius * radius;
+
+        self.pairs.iter().find_map(|pair| {
+            if within(&pair.a) {
+                Some(&pair.b)
+            } else if within(&pair.b) {
+                Some(&pair.a)
+            } else {
+                None
+            }
+        })
+    }
+}
+
+/// Marks a path segment as one end of a portal
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Portal;
+
+impl Component for Portal {
+    type Storage = NullStorage<Self>;
+}
+
+/// Stops a ball that's just come through a portal going straight back through it, it only
+/// counts down once the ball's rolled off
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PortalCooldown {
+    pub remaining: f32,
+}
+
+impl Component for PortalCooldown {
+    type Storage = VecStorage<Self>;
+}
+
+/// Picks pairs of plain segments a good way apart to join up and colours them in, the same seed
+/// always links up the same segments
+pub fn place_portals(
+    config: &WanderballConfig,
+    seed: u64,
+    path_segments: &mut [PathSegmentRecord],
+) -> Vec<PortalPair> {
+    let last = path_segments.len().saturating_sub(1);
+    // gates split the path into stretches, a portal only ever joins two ends of the same one or
+    // it'd be a way round a locked gate
+    let mut stretches: HashMap<String, Option<usize>> = HashMap::new();
+    let mut stretch = 0;
+    for segment in path_segments.iter() {
+        if segment.gate.is_some() {
+            stretch += 1;
+        }
+        let key = segment_key(&segment.transform);
+        let laid = stretches.entry(key).or_insert(Some(stretch));
+        if *laid != Some(stretch) {
+            // laid down again past a gate, so it isn't on either side
+            *laid = None;
+        }
+    }
+
+    let mut seen = HashSet::new();
+    // never where the ball starts, on the goal, on a checkpoint, on a gate or anywhere the
+    // surface is already doing something, and only once for segments laid down on top of one
+    // another
+    let mut candidates: Vec<(usize, PortalEnd)> = path_segments
+        .iter()
+        .enumerate()
+        .skip(1)
+        .filter(|(i, segment)| {
+            !(config.goal_mode && *i == last)
+                && !segment.checkpoint
+                && segment.gate.is_none()
+                && segment.surface == Surface::Plain
+        })
+        .filter_map(|(_, segment)| {
+            let key = segment_key(&segment.transform);
+            let stretch = stretches.get(&key).copied().flatten()?;
+            Some((
+                stretch,
+                PortalEnd {
+                    segment: key,
+                    x: segment.transform.translation().x,
+                    y: segment.transform.translation().y,
+                },
+            ))
+        })
+        .filter(|(_, end)| seen.insert(end.segment.clone()))
+        .collect();
+
+    // offset from the path's seed, and everything else placed on it
+    let mut rng = StdRng::seed_from_u64(seed.wrapping_add(5));
+    candidates.shuffle(&mut rng);
+
+    let mut pairs: Vec<PortalPair> = vec![];
+    while pairs.len() < config.portal_pairs && !candidates.is_empty() {
+        let (stretch, a) = candidates.remove(0);
+        let far_enough = candidates.iter().position(|(other, end)| {
+            *other == stretch
+                && (end.x - a.x).powi(2) + (end.y - a.y).powi(2)
+                    >= config.portal_min_distance * config.portal_min_distance
+        });
+
+        if let Some((_, b)) = far_enough.map(|index| candidates.remove(index)) {
+            pairs.push(PortalPair { a, b });
+        }
+    }
+
+    for (index, pair) in pairs.iter().enumerate() {
+        for segment in path_segments.iter_mut() {
+            let key = segment_key(&segment.transform);
+            if key == pair.a.segment || key == pair.b.segment {
+                segment.tint = PortalPair::tint(index);
+            }
+        }
+    }
+
+    pairs
+}
+
+pub fn load_portals(world: &mut World, pairs: Vec<PortalPair>) {
+    world.insert(Portals { pairs });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::gate::place_gates;
+    use crate::components::path::generate_path;
+
+    fn config() -> WanderballConfig {
+        WanderballConfig {
+            portal_min_distance: 50.0,
+            gates: 0,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn the_same_seed_places_the_same_portals() {
+        let config = config();
+        let mut first = generate_path(&config, 7, 200);
+        let mut second = generate_path(&config, 7, 200);
+
+        let portals = place_portals(&config, 7, &mut first);
+        assert!(!portals.is_empty());
+        assert_eq!(portals, place_portals(&config, 7, &mut second));
+    }
+
+    #[test]
+    fn portals_never_join_the_two_sides_of_a_gate() {
+        let config = WanderballConfig {
+            gates: 2,
+            ..config()
+        };
+        let mut path_segments = generate_path(&config, 7, 200);
+        place_gates(&config, 7, &mut path_segments);
+        let portals = place_portals(&config, 7, &mut path_segments);
+
+        // every stretch between gates each segment's laid down in
+        let mut stretches: HashMap<String, HashSet<usize>> = HashMap::new();
+        let mut stretch = 0;
+        for segment in &path_segments {
+            if segment.gate.is_some() {
+                stretch += 1;
+            }
+            stretches
+                .entry(segment_key(&segment.transform))
+                .or_default()
+                .insert(stretch);
+        }
+
+        assert!(stretch > 0);
+        for pair in portals {
+            let a = &stretches[&pair.a.segment];
+            assert_eq!(a.len(), 1);
+            assert_eq!(a, &stretches[&pair.b.segment]);
+        }
+    }
+
+    #[test]
+    fn portals_join_ends_far_enough_apart() {
+        let config = config();
+        let mut path_segments = generate_path(&config, 7, 200);
+
+        for pair in place_portals(&config, 7, &mut path_segments) {
+            let distance = (pair.a.x - pair.b.x).hypot(pair.a.y - pair.b.y);
+            assert!(distance >= config.portal_min_distance);
+            assert_ne!(pair.a.segment, pair.b.segment);
+        }
+    }
+}
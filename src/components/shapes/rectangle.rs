@@ -1,5 +1,8 @@
 use crate::side::Side;
-use amethyst::ecs::{Component, VecStorage};
+use amethyst::{
+    core::Transform,
+    ecs::{Component, VecStorage},
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -57,3 +60,12 @@ pub fn point_outside_rect(
         None
     }
 }
+
+/// How far a point is from the nearest edge of a rectangle, zero if it's inside
+pub fn distance_to_rect(x: f32, y: f32, transform: &Transform, rectangle: &Rectangle) -> f32 {
+    let half_width = rectangle.width * 0.5;
+    let half_height = rectangle.height * 0.5;
+    let dx = ((x - transform.translation().x).abs() - half_width).max(0.0);
+    let dy = ((y - transform.translation().y).abs() - half_height).max(0.0);
+    (dx * dx + dy * dy).sqrt()
+}
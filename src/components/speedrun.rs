@@ -0,0 +1,62 @@
+use amethyst::{ecs::World, prelude::*};
+
+use crate::components::level::Level;
+use crate::resources::locale::Locale;
+use crate::resources::records::{read_records, PersonalBestRecord};
+
+/// The clock for timer mode. It starts when the ball first moves and stops at the goal or once
+/// the whole path has been explored
+#[derive(Debug, Clone, Default)]
+pub struct Speedrun {
+    pub running: bool,
+    pub finished: bool,
+    /// loaded games could pause the clock between sessions so they don't count towards bests
+    pub eligible: bool,
+    pub elapsed: f32,
+    pub splits: Vec<f32>,
+    pub personal_best: Option<PersonalBestRecord>,
+}
+
+impl Speedrun {
+    /// How far ahead (negative) or behind (positive) the personal best we were at the last split.
+    /// A loaded game's clock started over when it was loaded, so it isn't compared.
+    pub fn delta(&self) -> Option<f32> {
+        if !self.eligible {
+            return None;
+        }
+        let personal_best = self.personal_best.as_ref()?;
+        let index = self.splits.len().checked_sub(1)?;
+        let best_split = personal_best.splits.get(index)?;
+
+        Some(self.splits[index] - best_split)
+    }
+
+    pub fn text(&self, locale: &Locale) -> String {
+        let elapsed = format_timer(self.elapsed);
+        match self.delta() {
+            Some(delta) if delta < 0.0 => {
+                locale.format("hud.timer_ahead", &[&elapsed, &format!("{:.1}", -delta)])
+            }
+            Some(delta) => locale.format("hud.timer_behind", &[&elapsed, &format!("{:.1}", delta)]),
+            None => locale.format("hud.timer", &[&elapsed]),
+        }
+    }
+}
+
+/// Turns a number of seconds into something like "12:04.3"
+pub fn format_timer(seconds: f32) -> String {
+    let seconds = seconds.max(0.0);
+    let tenths = ((seconds * 10.0) as u32) % 10;
+    let whole = seconds as u32;
+    format!("{}:{:02}.{}", whole / 60, whole % 60, tenths)
+}
+
+pub fn init_speedrun(world: &mut World, eligible: bool) {
+    let seed = world.read_resource::<Level>().seed.to_string();
+    let personal_best = read_records().personal_bests.remove(&seed);
+    world.insert(Speedrun {
+        eligible,
+        personal_best,
+        ..Default::default()
+    });
+}
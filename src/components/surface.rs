@@ -0,0 +1,152 @@
+use amethyst::{
+    ecs::{Component, VecStorage},
+    renderer::{palette::Srgba, resources::Tint},
+};
+
+use rand::Rng;
+
+use crate::config::WanderballConfig;
+use crate::resources::{controls::Movement, save::PathSegmentRecord};
+use crate::side::Side;
+use serde::{Deserialize, Serialize};
+
+/// Anything slower than this on ice has come to a stop
+const STOPPED: f32 = 0.01;
+
+/// What a path segment is made of, and so how the ball rolls over it
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum Surface {
+    Plain,
+    /// the ball keeps going the way it was and is slow to change direction
+    Ice,
+    /// everything's slower
+    Mud,
+    /// everything's faster
+    Boost,
+    /// pushes the ball along towards one end, hard going the other way
+    Conveyor(Side),
+}
+
+impl Default for Surface {
+    fn default() -> Self {
+        Surface::Plain
+    }
+}
+
+impl Component for Surface {
+    type Storage = VecStorage<Self>;
+}
+
+impl Surface {
+    /// Anything but plain, pointing along the segment if it's a conveyor
+    pub fn random<R: Rng>(rng: &mut R, horizontal: bool) -> Surface {
+        match rng.gen_range(0, 4) {
+            0 => Surface::Ice,
+            1 => Surface::Mud,
+            2 => Surface::Boost,
+            _ => Surface::Conveyor(match (horizontal, rng.gen_bool(0.5)) {
+                (true, true) => Side::Right,
+                (true, false) => Side::Left,
+                (false, true) => Side::Top,
+                (false, false) => Side::Bottom,
+            }),
+        }
+    }
+
+    pub fn tint(self) -> Tint {
+        match self {
+            Surface::Plain => Tint(Srgba::new(1.0, 1.0, 1.0, 1.0)),
+            Surface::Ice => Tint(Srgba::new(0.6, 0.9, 1.0, 1.0)),
+            Surface::Mud => Tint(Srgba::new(0.6, 0.45, 0.3, 1.0)),
+            Surface::Boost => Tint(Srgba::new(1.0, 0.85, 0.3, 1.0)),
+            Surface::Conveyor(_) => Tint(Srgba::new(0.75, 0.5, 0.9, 1.0)),
+        }
+    }
+
+    /// A little dimmer once the ball's been over it, so it's still obvious what it's made of
+    pub fn visited_tint(self) -> Tint {
+        let Tint(color) = self.tint();
+        Tint(Srgba::new(
+            color.red * 0.95,
+            color.green * 0.95,
+            color.blue * 0.95,
+            color.alpha,
+        ))
+    }
+
+    /// What the ball actually does with the controls it's given while it's on this surface
+    pub fn apply(
+        self,
+        input: Movement,
+        momentum: &mut Momentum,
+        config: &WanderballConfig,
+    ) -> Movement {
+        let want_x = input.move_x.unwrap_or(0.0);
+        let want_y = input.move_y.unwrap_or(0.0);
+
+        if self == Surface::Ice {
+            momentum.x += (want_x - momentum.x) * config.ice_friction;
+            momentum.y += (want_y - momentum.y) * config.ice_friction;
+            if momentum.x.abs() < STOPPED {
+                momentum.x = 0.0;
+            }
+            if momentum.y.abs() < STOPPED {
+                momentum.y = 0.0;
+            }
+        } else {
+            momentum.x = want_x;
+            momentum.y = want_y;
+        }
+
+        let (mut x, mut y) = (momentum.x, momentum.y);
+        match self {
+            Surface::Mud => {
+                x *= config.mud_factor;
+                y *= config.mud_factor;
+            }
+            Surface::Boost => {
+                x *= config.boost_factor;
+                y *= config.boost_factor;
+            }
+            Surface::Conveyor(side) => match side {
+                Side::Left => x -= config.conveyor_speed,
+                Side::Right => x += config.conveyor_speed,
+                Side::Bottom => y -= config.conveyor_speed,
+                Side::Top => y += config.conveyor_speed,
+            },
+            Surface::Plain | Surface::Ice => {}
+        }
+
+        Movement {
+            move_x: Some(x),
+            move_y: Some(y),
+        }
+    }
+}
+
+/// How fast a ball's rolling, only really matters on ice where it carries on after letting go
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Momentum {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Component for Momentum {
+    type Storage = VecStorage<Self>;
+}
+
+/// The surface under a point, whichever segment was laid down first wins where they overlap
+pub fn surface_at<F>(x: f32, y: f32, path_segments: &[PathSegmentRecord], open: F) -> Surface
+where
+    F: Fn(&PathSegmentRecord) -> bool,
+{
+    path_segments
+        .iter()
+        .find(|segment| {
+            open(segment)
+                && (x - segment.transform.translation().x).abs() <= segment.rectangle.width * 0.5
+                && (y - segment.transform.translation().y).abs() <= segment.rectangle.height * 0.5
+        })
+        .map(|segment| segment.surface)
+        .unwrap_or_default()
+}
@@ -8,6 +8,21 @@ use crate::config::WanderballConfig;
 use crate::resources::save::VideographerRecord;
 use serde::{Deserialize, Serialize};
 
+/// How the videographer keeps the balls on screen
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum CameraMode {
+    /// stays put until the balls roll off the edge, then moves over a whole view
+    Flip,
+    /// keeps the balls in the middle of the view
+    Follow,
+}
+
+impl Default for CameraMode {
+    fn default() -> Self {
+        CameraMode::Flip
+    }
+}
+
 /// The entity that holds the camera and moves it when it needs to
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct Videographer {
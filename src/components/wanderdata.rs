@@ -1,12 +1,9 @@
-use crate::components::ball::Ball;
-use crate::config::WanderballConfig;
-use crate::resources::save::PedometerRecord;
+use crate::components::path::unique_segment_count;
+use crate::resources::locale::Locale;
+use crate::resources::save::{PathSegmentRecord, PedometerRecord};
 use amethyst::{
-    assets::Loader,
-    core::transform::Transform,
-    ecs::{Component, Entity, Join, VecStorage, World},
+    ecs::{Component, VecStorage, World},
     prelude::*,
-    ui::{Anchor, LineMode, TtfFormat, UiText, UiTransform},
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -21,204 +18,138 @@ impl Component for Coordinate {
     type Storage = VecStorage<Self>;
 }
 
-pub struct CoordinateText {
-    pub coordinates: Entity,
-}
-
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct Pedometer {
     pub steps: i32,
     pub visited: HashMap<String, ()>,
+    pub distance: f32,
+    pub time_played: f32,
+    pub streak: i32,
+    pub longest_streak: i32,
+    pub current_segment: Option<String>,
+    pub segment_count: usize,
 }
 
 impl Component for Pedometer {
     type Storage = VecStorage<Self>;
 }
 
-pub struct PedometerText {
-    pub steps: Entity,
-}
+impl Pedometer {
+    /// Called whenever the ball rolls onto a segment, only segments we haven't visited before
+    /// count as steps and keep a streak going
+    pub fn step_on(&mut self, segment: String) {
+        if self.current_segment.as_ref() == Some(&segment) {
+            return;
+        }
+
+        if self.visited.contains_key(&segment) {
+            self.streak = 0;
+        } else {
+            self.steps += 1;
+            self.streak += 1;
+            self.longest_streak = self.longest_streak.max(self.streak);
+            self.visited.insert(segment.clone(), ());
+        }
+
+        self.current_segment = Some(segment);
+    }
 
-pub fn init_coordinates(world: &mut World) {
-    let mut ball_x: f32 = 0.0;
-    let mut ball_y: f32 = 0.0;
-    {
-        let ball_storage = world.read_storage::<Ball>();
-        let transform_storage = world.read_storage::<Transform>();
+    /// Percentage of the path that's been visited
+    pub fn exploration(&self) -> f32 {
+        if self.segment_count == 0 {
+            0.0
+        } else {
+            (self.visited.len() as f32 / self.segment_count as f32 * 100.0).min(100.0)
+        }
+    }
 
-        for (_, transform) in (&ball_storage, &transform_storage).join() {
-            ball_x = transform.translation().x;
-            ball_y = transform.translation().y;
+    pub fn segments_per_minute(&self) -> f32 {
+        if self.time_played <= 0.0 {
+            0.0
+        } else {
+            self.steps as f32 / (self.time_played / 60.0)
         }
     }
 
-    let (
-        wanderdata_display_left_x,
-        wanderdata_display_y,
-        wanderdata_display_z,
-        wanderdata_display_width,
-        wanderdata_display_height,
-        wanderdata_display_color,
-        wanderdata_display_font_size,
-    ) = {
-        let config = &world.read_resource::<WanderballConfig>();
-        (
-            config.wanderdata_display_left_x,
-            config.wanderdata_display_y,
-            config.wanderdata_display_z,
-            config.wanderdata_display_width,
-            config.wanderdata_display_height,
-            config.wanderdata_display_color,
-            config.wanderdata_display_font_size,
+    /// Brings the totals for everyone up to date from each player's own pedometer. Segments
+    /// count once however many players have been on them, distances add up, and the time
+    /// played is kept here rather than per player.
+    pub fn combine(&mut self, players: &[&Pedometer]) {
+        for player in players {
+            for segment in player.visited.keys() {
+                if !self.visited.contains_key(segment) {
+                    self.visited.insert(segment.clone(), ());
+                }
+            }
+        }
+
+        self.steps = self.visited.len() as i32;
+        self.distance = players.iter().map(|player| player.distance).sum();
+        self.streak = players
+            .iter()
+            .map(|player| player.streak)
+            .max()
+            .unwrap_or(0);
+        self.longest_streak = players
+            .iter()
+            .map(|player| player.longest_streak)
+            .fold(self.longest_streak, i32::max);
+        self.current_segment = players
+            .first()
+            .and_then(|player| player.current_segment.clone());
+    }
+
+    pub fn stats(&self, locale: &Locale) -> String {
+        locale.format(
+            "hud.stats",
+            &[
+                &format!("{:.1}", self.exploration()),
+                &format!("{:.0}", self.distance),
+                &format_duration(self.time_played),
+                &self.longest_streak,
+                &format!("{:.1}", self.segments_per_minute()),
+            ],
         )
-    };
-
-    let font = world.read_resource::<Loader>().load(
-        "font/square.ttf",
-        TtfFormat,
-        (),
-        &world.read_resource(),
-    );
-    let coordinate_transform = UiTransform::new(
-        "coordinates".to_string(),
-        Anchor::TopLeft,
-        Anchor::TopLeft,
-        wanderdata_display_left_x,
-        wanderdata_display_y,
-        wanderdata_display_z,
-        wanderdata_display_width,
-        wanderdata_display_height,
-    );
-
-    let coordinates = world
-        .create_entity()
-        .with(coordinate_transform)
-        .with(UiText::new(
-            font,
-            format!("({},{})", ball_x.to_string(), ball_y.to_string()),
-            wanderdata_display_color,
-            wanderdata_display_font_size,
-            LineMode::Single,
-            Anchor::Middle,
-        ))
-        .build();
-
-    world.insert(CoordinateText { coordinates });
+    }
+}
+
+/// Turns a number of seconds into something like "12:04"
+pub fn format_duration(seconds: f32) -> String {
+    let seconds = seconds.max(0.0) as u32;
+    format!("{}:{:02}", seconds / 60, seconds % 60)
 }
 
 pub fn load_pedometer(world: &mut World, pedometer_record: PedometerRecord) {
-    let (
-        wanderdata_display_right_x,
-        wanderdata_display_y,
-        wanderdata_display_z,
-        wanderdata_display_width,
-        wanderdata_display_height,
-        wanderdata_display_color,
-        wanderdata_display_font_size,
-    ) = {
-        let config = &world.read_resource::<WanderballConfig>();
-        (
-            config.wanderdata_display_right_x,
-            config.wanderdata_display_y,
-            config.wanderdata_display_z,
-            config.wanderdata_display_width,
-            config.wanderdata_display_height,
-            config.wanderdata_display_color,
-            config.wanderdata_display_font_size,
-        )
-    };
-
-    let font = world.read_resource::<Loader>().load(
-        "font/square.ttf",
-        TtfFormat,
-        (),
-        &world.read_resource(),
-    );
-
-    let pedometer_transform = UiTransform::new(
-        "pedometer".to_string(),
-        Anchor::TopRight,
-        Anchor::TopRight,
-        wanderdata_display_right_x,
-        wanderdata_display_y,
-        wanderdata_display_z,
-        wanderdata_display_width,
-        wanderdata_display_height,
-    );
-
-    let steps = world
-        .create_entity()
-        .with(pedometer_transform)
-        .with(UiText::new(
-            font,
-            format!("(path steps: {})", pedometer_record.steps),
-            wanderdata_display_color,
-            wanderdata_display_font_size,
-            LineMode::Single,
-            Anchor::Middle,
-        ))
-        .build();
-
-    world.insert(PedometerText { steps });
+    let segment_count = unique_segment_count(&world.read_resource::<Vec<PathSegmentRecord>>());
+    world.insert(pedometer_from_record(pedometer_record, segment_count))
+}
 
-    world.insert(Pedometer {
+pub fn pedometer_from_record(pedometer_record: PedometerRecord, segment_count: usize) -> Pedometer {
+    Pedometer {
         steps: pedometer_record.steps,
         visited: pedometer_record.visited,
-    })
+        distance: pedometer_record.distance,
+        time_played: pedometer_record.time_played,
+        longest_streak: pedometer_record.longest_streak,
+        segment_count,
+        ..Default::default()
+    }
+}
+
+pub fn pedometer_record(pedometer: &Pedometer) -> PedometerRecord {
+    PedometerRecord {
+        steps: pedometer.steps,
+        visited: pedometer.visited.clone(),
+        distance: pedometer.distance,
+        time_played: pedometer.time_played,
+        longest_streak: pedometer.longest_streak,
+    }
 }
 
 pub fn init_pedometer(world: &mut World) {
-    let (
-        wanderdata_display_right_x,
-        wanderdata_display_y,
-        wanderdata_display_z,
-        wanderdata_display_width,
-        wanderdata_display_height,
-        wanderdata_display_color,
-        wanderdata_display_font_size,
-    ) = {
-        let config = &world.read_resource::<WanderballConfig>();
-        (
-            config.wanderdata_display_right_x,
-            config.wanderdata_display_y,
-            config.wanderdata_display_z,
-            config.wanderdata_display_width,
-            config.wanderdata_display_height,
-            config.wanderdata_display_color,
-            config.wanderdata_display_font_size,
-        )
-    };
-
-    let font = world.read_resource::<Loader>().load(
-        "font/square.ttf",
-        TtfFormat,
-        (),
-        &world.read_resource(),
-    );
-
-    let pedometer_transform = UiTransform::new(
-        "pedometer".to_string(),
-        Anchor::TopRight,
-        Anchor::TopRight,
-        wanderdata_display_right_x,
-        wanderdata_display_y,
-        wanderdata_display_z,
-        wanderdata_display_width,
-        wanderdata_display_height,
-    );
-
-    let steps = world
-        .create_entity()
-        .with(pedometer_transform)
-        .with(UiText::new(
-            font,
-            format!("(path steps: {})", 0.0),
-            wanderdata_display_color,
-            wanderdata_display_font_size,
-            LineMode::Single,
-            Anchor::Middle,
-        ))
-        .build();
-
-    world.insert(PedometerText { steps });
+    let segment_count = unique_segment_count(&world.read_resource::<Vec<PathSegmentRecord>>());
+    world.insert(Pedometer {
+        segment_count,
+        ..Default::default()
+    })
 }
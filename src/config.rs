@@ -1,3 +1,7 @@
+use crate::components::hud::HudSource;
+use crate::components::npc::Behavior;
+use crate::components::videographer::CameraMode;
+use amethyst::ui::Anchor;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -12,18 +16,46 @@ pub struct WanderballConfig {
     pub path_segment_height: f32,
     pub path_segment_width: f32,
     pub path_length: usize,
+    pub path_length_increment: usize,
+    pub goal_mode: bool,
+    /// how far from the start the goal has to be, the path carries on until it's that far out
+    pub goal_min_distance: f32,
+    pub speedrun_mode: bool,
+    pub speedrun_split_interval: f32,
+    pub record_replays: bool,
+    pub ghost_replay: Option<String>,
+    pub players: usize,
+    pub race_exploration: f32,
+    pub npcs: Vec<Behavior>,
+    pub npc_speed: f32,
+    pub autopilot: bool,
+    pub autopilot_stuck_frames: usize,
+    pub autopilot_max_frames: usize,
+    pub reveal_radius: f32,
+    pub collectible_chance: f32,
+    pub collectible_radius: f32,
+    pub surface_chance: f32,
+    pub ice_friction: f32,
+    pub mud_factor: f32,
+    pub boost_factor: f32,
+    pub conveyor_speed: f32,
+    pub checkpoint_interval: usize,
+    pub hazard_chance: f32,
+    pub hazard_speed: f32,
+    pub hazard_size: f32,
+    pub gates: usize,
+    pub portal_pairs: usize,
+    pub portal_min_distance: f32,
+    pub portal_radius: f32,
+    pub portal_cooldown: f32,
     pub move_factor: f32,
     pub fast_move_factor: f32,
     pub zoom_factor: f32,
     pub fast_zoom_factor: f32,
-    pub wanderdata_display_font_size: f32,
-    pub wanderdata_display_left_x: f32,
-    pub wanderdata_display_right_x: f32,
-    pub wanderdata_display_y: f32,
-    pub wanderdata_display_z: f32,
-    pub wanderdata_display_width: f32,
-    pub wanderdata_display_height: f32,
-    pub wanderdata_display_color: [f32; 4],
+    pub camera_mode: CameraMode,
+    pub music_volume: f32,
+    pub show_hud: bool,
+    pub summary_on_close: bool,
 }
 
 impl Default for WanderballConfig {
@@ -39,18 +71,104 @@ impl Default for WanderballConfig {
             path_segment_height: 8.0,
             path_segment_width: 24.0,
             path_length: 100,
+            path_length_increment: 100,
+            goal_mode: false,
+            goal_min_distance: 200.0,
+            speedrun_mode: false,
+            speedrun_split_interval: 10.0,
+            record_replays: true,
+            ghost_replay: None,
+            players: 1,
+            race_exploration: 25.0,
+            npcs: vec![],
+            npc_speed: 30.0,
+            autopilot: false,
+            autopilot_stuck_frames: 120,
+            autopilot_max_frames: 1_000_000,
+            reveal_radius: 30.0,
+            collectible_chance: 0.05,
+            collectible_radius: 3.0,
+            surface_chance: 0.1,
+            ice_friction: 0.05,
+            mud_factor: 0.5,
+            boost_factor: 2.0,
+            conveyor_speed: 0.6,
+            checkpoint_interval: 25,
+            hazard_chance: 0.05,
+            hazard_speed: 10.0,
+            hazard_size: 3.0,
+            gates: 2,
+            portal_pairs: 3,
+            portal_min_distance: 300.0,
+            portal_radius: 3.0,
+            portal_cooldown: 1.0,
             move_factor: 0.5,
             fast_move_factor: 1.0,
             zoom_factor: 50.0,
             fast_zoom_factor: 10.0,
-            wanderdata_display_font_size: 10.0,
-            wanderdata_display_left_x: 2.0,
-            wanderdata_display_right_x: 2.0,
-            wanderdata_display_y: -50.0,
-            wanderdata_display_z: 1.0,
-            wanderdata_display_width: 1000.0,
-            wanderdata_display_height: 10.0,
-            wanderdata_display_color: [1.0, 1.0, 1.0, 1.0],
+            camera_mode: CameraMode::Flip,
+            music_volume: 0.2,
+            show_hud: true,
+            summary_on_close: true,
         }
     }
 }
+
+impl WanderballConfig {
+    /// Chances go from never to always, anything outside that is pulled back in rather than
+    /// left to blow up the first time something's placed with it
+    pub fn clamp_chances(&mut self) {
+        for (name, chance) in [
+            ("collectible_chance", &mut self.collectible_chance),
+            ("surface_chance", &mut self.surface_chance),
+            ("hazard_chance", &mut self.hazard_chance),
+        ]
+        .iter_mut()
+        {
+            let value: f32 = **chance;
+            if !(0.0..=1.0).contains(&value) {
+                let clamped = if value.is_nan() {
+                    0.0
+                } else {
+                    value.clamp(0.0, 1.0)
+                };
+                log::warn!("{} of {} isn't a chance, using {}", name, value, clamped);
+                **chance = clamped;
+            }
+        }
+    }
+}
+
+/// What's shown on the HUD and where, read from config/hud.ron
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct HudLayout {
+    pub font: String,
+    pub font_size: f32,
+    pub color: [f32; 4],
+    pub z: f32,
+    pub widgets: Vec<HudWidgetLayout>,
+}
+
+/// One line of text on the HUD, the font, size and color fall back to the layout's own
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct HudWidgetLayout {
+    pub id: String,
+    pub source: HudSource,
+    pub anchor: Anchor,
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    #[serde(default = "middle")]
+    pub align: Anchor,
+    #[serde(default)]
+    pub font: Option<String>,
+    #[serde(default)]
+    pub font_size: Option<f32>,
+    #[serde(default)]
+    pub color: Option<[f32; 4]>,
+}
+
+fn middle() -> Anchor {
+    Anchor::Middle
+}
@@ -5,18 +5,21 @@ use amethyst::{
     audio::{AudioBundle, DjSystemDesc},
     config::Config,
     core::transform::TransformBundle,
-    input::{InputBundle, StringBindings},
-    prelude::{Application, GameDataBuilder},
+    input::InputBundle,
+    prelude::{CoreApplication, GameDataBuilder},
     renderer::{
         plugins::{RenderFlat2D, RenderToWindow},
         types::DefaultBackend,
         RenderingBundle,
     },
     ui::{RenderUi, UiBundle},
-    utils::application_root_dir,
+    utils::{application_root_dir, fps_counter::FpsCounterBundle},
+    window::DisplayConfig,
+    StateEventReader,
 };
 
 mod audio;
+mod autopilot;
 mod bundle;
 mod camera;
 mod components;
@@ -29,8 +32,12 @@ mod systems;
 
 use crate::audio::Music;
 use crate::bundle::WanderballBundle;
-use crate::config::WanderballConfig;
-use crate::states::start::StartScreen;
+use crate::config::{HudLayout, WanderballConfig};
+use crate::resources::bindings::{read_bindings, GameBindings};
+use crate::resources::locale::{Locale, FALLBACK_LANGUAGE};
+use crate::resources::preferences::read_preferences;
+use crate::resources::saves::migrate_old_save;
+use crate::states::{start::StartScreen, GameStateEvent};
 
 pub fn run() -> amethyst::Result<()> {
     amethyst::start_logger(Default::default());
@@ -39,24 +46,37 @@ pub fn run() -> amethyst::Result<()> {
     let display_config_path = config_path.join("display.ron");
     let binding_path = config_path.join("bindings.ron");
     let wanderball_config_path = config_path.join("wanderball.ron");
-    let wanderball_config: WanderballConfig = Config::load(wanderball_config_path)?;
+    let mut wanderball_config: WanderballConfig = Config::load(wanderball_config_path)?;
+    wanderball_config.clamp_chances();
+    let hud_layout: HudLayout = Config::load(config_path.join("hud.ron"))?;
 
+    migrate_old_save();
+
+    // anything changed in the settings menu last time wins over the config files
+    let preferences = read_preferences();
+    preferences.apply(&mut wanderball_config);
+    let mut display_config: DisplayConfig = Config::load(display_config_path)?;
+    if let Some(window_size) = preferences.window_size {
+        display_config.dimensions = Some(window_size);
+    }
+
+    // rebinding keys in game saves them over the ones in bindings.ron
     let input_bundle =
-        InputBundle::<StringBindings>::new().with_bindings_from_file(binding_path)?;
+        InputBundle::<GameBindings>::new().with_bindings(read_bindings(&binding_path)?);
 
     let game_data = GameDataBuilder::default()
         .with_bundle(TransformBundle::new())?
         .with_bundle(
             RenderingBundle::<DefaultBackend>::new()
                 .with_plugin(
-                    RenderToWindow::from_config_path(display_config_path)?
-                        .with_clear([0.0, 0.0, 0.0, 1.0]),
+                    RenderToWindow::from_config(display_config).with_clear([0.0, 0.0, 0.0, 1.0]),
                 )
                 .with_plugin(RenderUi::default())
                 .with_plugin(RenderFlat2D::default()),
         )?
         .with_bundle(input_bundle)?
-        .with_bundle(UiBundle::<StringBindings>::new())?
+        .with_bundle(UiBundle::<GameBindings>::new())?
+        .with_bundle(FpsCounterBundle)?
         .with_bundle(WanderballBundle)?
         .with_bundle(AudioBundle::default())?
         .with_system_desc(
@@ -66,9 +86,34 @@ pub fn run() -> amethyst::Result<()> {
         );
 
     let assets_dir = app_root.join("assets");
-    let mut game = Application::build(assets_dir, StartScreen::default())?
-        .with_resource(wanderball_config)
-        .build(game_data)?;
+    let locale = Locale::load(
+        &assets_dir.join("i18n"),
+        preferences.language.as_deref().unwrap_or(FALLBACK_LANGUAGE),
+    );
+    let mut game = CoreApplication::<_, GameStateEvent, StateEventReader<GameBindings>>::build(
+        assets_dir,
+        StartScreen::default(),
+    )?
+    .with_resource(wanderball_config)
+    .with_resource(hud_layout)
+    .with_resource(preferences)
+    .with_resource(locale)
+    .build(game_data)?;
     game.run();
     Ok(())
 }
+
+/// Sends the autopilot round the path for a seed with no window and prints how it got on, a
+/// random seed if there isn't one
+pub fn explore(seed: Option<u64>) -> amethyst::Result<()> {
+    let app_root = application_root_dir()?;
+    let mut wanderball_config: WanderballConfig =
+        Config::load(app_root.join("config").join("wanderball.ron"))?;
+    wanderball_config.clamp_chances();
+
+    let seed = seed.unwrap_or_else(rand::random);
+    let report = autopilot::explore(&wanderball_config, seed, wanderball_config.path_length);
+    print!("{}", report);
+
+    Ok(())
+}
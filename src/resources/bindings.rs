@@ -0,0 +1,438 @@
+use amethyst::{
+    config::{Config, ConfigError},
+    input::{Axis, BindingTypes, Bindings, Button, StringBindings},
+};
+use serde::{Deserialize, Serialize};
+use std::{fs::create_dir, path::Path};
+
+use crate::components::player::{Player, MAX_PLAYERS};
+
+const BINDINGS_FILE: &str = "wanderball-bindings.ron";
+
+/// The game's own names for its inputs, anything in bindings.ron that isn't one of these is an
+/// error rather than a binding nothing ever reads
+#[derive(Debug)]
+pub struct GameBindings;
+
+impl BindingTypes for GameBindings {
+    type Axis = AxisBinding;
+    type Action = ActionBinding;
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum AxisBinding {
+    /// left and right for a player, by index
+    MoveX(usize),
+    /// down and up for a player, by index
+    MoveY(usize),
+    Zoom,
+}
+
+impl AxisBinding {
+    pub fn name(self) -> String {
+        match self {
+            AxisBinding::MoveX(player) => format!("{} move x", Player::new(player).name()),
+            AxisBinding::MoveY(player) => format!("{} move y", Player::new(player).name()),
+            AxisBinding::Zoom => "zoom".to_string(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum ActionBinding {
+    FastMovement,
+    Pause,
+}
+
+impl ActionBinding {
+    pub fn name(self) -> &'static str {
+        match self {
+            ActionBinding::FastMovement => "fast movement",
+            ActionBinding::Pause => "pause",
+        }
+    }
+}
+
+/// One key that can be rebound, either end of an axis or an action
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BindingSlot {
+    Axis { axis: AxisBinding, positive: bool },
+    Action(ActionBinding),
+}
+
+impl BindingSlot {
+    fn axis(axis: AxisBinding, positive: bool) -> BindingSlot {
+        BindingSlot::Axis { axis, positive }
+    }
+
+    /// The key it's bound to, the first one for actions that have more than one
+    pub fn button(&self, bindings: &Bindings<GameBindings>) -> Option<Button> {
+        match self {
+            BindingSlot::Axis { axis, positive } => match bindings.axis(axis)? {
+                Axis::Emulated { pos, neg } => Some(if *positive { *pos } else { *neg }),
+                _ => None,
+            },
+            BindingSlot::Action(action) => bindings
+                .action_bindings(action)
+                .find(|combination| combination.len() == 1)
+                .map(|combination| combination[0]),
+        }
+    }
+
+    /// Whether the key's on this slot, for actions that's any combination of just that key
+    fn is_bound_to(&self, bindings: &Bindings<GameBindings>, button: Button) -> bool {
+        match self {
+            BindingSlot::Axis { .. } => self.button(bindings) == Some(button),
+            BindingSlot::Action(action) => bindings
+                .action_bindings(action)
+                .any(|combination| combination == [button]),
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            BindingSlot::Axis { axis, positive } => {
+                format!("{} {}", axis.name(), if *positive { "+" } else { "-" })
+            }
+            BindingSlot::Action(action) => action.name().to_string(),
+        }
+    }
+}
+
+/// What each player can rebind, in the order the controls screen lists them. The zoom, fast
+/// movement and pause are everyone's.
+pub fn rebindable(player: Player) -> Vec<BindingSlot> {
+    vec![
+        BindingSlot::axis(AxisBinding::MoveX(player.index), true),
+        BindingSlot::axis(AxisBinding::MoveX(player.index), false),
+        BindingSlot::axis(AxisBinding::MoveY(player.index), true),
+        BindingSlot::axis(AxisBinding::MoveY(player.index), false),
+        BindingSlot::axis(AxisBinding::Zoom, true),
+        BindingSlot::axis(AxisBinding::Zoom, false),
+        BindingSlot::Action(ActionBinding::FastMovement),
+        BindingSlot::Action(ActionBinding::Pause),
+    ]
+}
+
+fn all_rebindable() -> Vec<BindingSlot> {
+    let mut slots: Vec<BindingSlot> = vec![];
+    for slot in (0..MAX_PLAYERS).map(Player::new).flat_map(rebindable) {
+        if !slots.contains(&slot) {
+            slots.push(slot);
+        }
+    }
+    slots
+}
+
+/// Why a key couldn't be bound
+#[derive(Debug, Clone)]
+pub enum RebindError {
+    /// another slot has the key and this one has nothing to give it in return
+    Taken(BindingSlot),
+    Failed(String),
+}
+
+/// Binds a key, and if it was already bound somewhere else that gets this slot's old key so
+/// nothing ends up on two things at once. Hands back the new bindings and whatever the key was
+/// taken from. Actions keep any other keys and combinations they have.
+pub fn rebind(
+    bindings: &Bindings<GameBindings>,
+    slot: &BindingSlot,
+    button: Button,
+) -> Result<(Bindings<GameBindings>, Option<BindingSlot>), RebindError> {
+    let old = slot.button(bindings);
+    let conflict = all_rebindable()
+        .into_iter()
+        .find(|other| other != slot && other.is_bound_to(bindings, button));
+
+    // swapping nothing over would leave the other slot without a key
+    if let (Some(conflict), None) = (conflict, old) {
+        return Err(RebindError::Taken(conflict));
+    }
+    let replace = |other: &BindingSlot, current: Button| match old {
+        _ if other == slot => button,
+        Some(old) if Some(*other) == conflict => old,
+        _ => current,
+    };
+
+    // rebuilt from scratch, amethyst won't let a key be bound twice even for a moment
+    let mut rebound = Bindings::<GameBindings>::new();
+    for axis in bindings.axes() {
+        let bound = match bindings.axis(axis) {
+            Some(Axis::Emulated { pos, neg }) => Axis::Emulated {
+                pos: replace(&BindingSlot::axis(*axis, true), *pos),
+                neg: replace(&BindingSlot::axis(*axis, false), *neg),
+            },
+            Some(other) => other.clone(),
+            None => continue,
+        };
+        rebound
+            .insert_axis(*axis, bound)
+            .map_err(|error| RebindError::Failed(error.to_string()))?;
+    }
+    for action in bindings.actions() {
+        let action_slot = BindingSlot::Action(*action);
+        let mut combinations: Vec<Vec<Button>> = bindings
+            .action_bindings(action)
+            .map(|combination| combination.to_vec())
+            .collect();
+        let has_button = combinations
+            .iter()
+            .any(|combination| *combination == [button]);
+        if action_slot == *slot && !has_button {
+            // only the key the controls screen shows is swapped, the rest stay as they were
+            match combinations
+                .iter_mut()
+                .find(|combination| old.map_or(false, |old| **combination == [old]))
+            {
+                Some(combination) => *combination = vec![button],
+                None => combinations.push(vec![button]),
+            }
+        } else if Some(action_slot) == conflict {
+            if let (Some(old), Some(combination)) = (
+                old,
+                combinations
+                    .iter_mut()
+                    .find(|combination| **combination == [button]),
+            ) {
+                *combination = vec![old];
+            }
+        }
+        for combination in combinations {
+            rebound
+                .insert_action_binding(*action, combination)
+                .map_err(|error| RebindError::Failed(error.to_string()))?;
+        }
+    }
+    // bindings saved before an action was added don't have it yet
+    if let BindingSlot::Action(action) = slot {
+        if !bindings.actions().any(|other| other == action) {
+            rebound
+                .insert_action_binding(*action, vec![button])
+                .map_err(|error| RebindError::Failed(error.to_string()))?;
+        }
+    }
+
+    Ok((rebound, conflict))
+}
+
+/// The player's own bindings if they've changed any, otherwise the ones in config/bindings.ron
+pub fn read_bindings(default_path: &Path) -> Result<Bindings<GameBindings>, ConfigError> {
+    let bindings_file_path = Path::new(".save").join(BINDINGS_FILE);
+
+    if bindings_file_path.exists() {
+        match Bindings::<GameBindings>::load(&bindings_file_path) {
+            Ok(bindings) => return Ok(bindings),
+            Err(error) => match migrate_bindings(&bindings_file_path) {
+                Some(bindings) => {
+                    log::info!("moved the saved bindings over from their old names");
+                    write_bindings(&bindings);
+                    return Ok(bindings);
+                }
+                None => log::error!("couldn't read the saved bindings: {}", error),
+            },
+        }
+    }
+
+    Bindings::<GameBindings>::load(default_path)
+}
+
+/// Saved bindings from when they were named with strings like "p1_move_x" and "pause", turned
+/// into the ones the game has now. Anything that doesn't match up gives up on the whole file.
+fn migrate_bindings(path: &Path) -> Option<Bindings<GameBindings>> {
+    let old = Bindings::<StringBindings>::load(path).ok()?;
+    let mut bindings = Bindings::<GameBindings>::new();
+
+    for name in old.axes() {
+        bindings
+            .insert_axis(axis_from_name(name)?, old.axis(name)?.clone())
+            .ok()?;
+    }
+    for name in old.actions() {
+        let action = match name.as_str() {
+            "fast_movement" => ActionBinding::FastMovement,
+            "pause" => ActionBinding::Pause,
+            _ => return None,
+        };
+        for combination in old.action_bindings(name) {
+            bindings
+                .insert_action_binding(action, combination.to_vec())
+                .ok()?;
+        }
+    }
+
+    Some(bindings)
+}
+
+fn axis_from_name(name: &str) -> Option<AxisBinding> {
+    if name == "zoom" {
+        return Some(AxisBinding::Zoom);
+    }
+
+    let (player, axis) = name.strip_prefix('p')?.split_once('_')?;
+    let index = player.parse::<usize>().ok()?.checked_sub(1)?;
+    match axis {
+        "move_x" => Some(AxisBinding::MoveX(index)),
+        "move_y" => Some(AxisBinding::MoveY(index)),
+        _ => None,
+    }
+}
+
+pub fn write_bindings(bindings: &Bindings<GameBindings>) {
+    let save_dir = Path::new(".save");
+    if !save_dir.exists() && create_dir(save_dir).is_err() {
+        log::error!("couldn't create the save directory for bindings");
+        return;
+    }
+
+    match bindings.write(save_dir.join(BINDINGS_FILE)) {
+        Ok(()) => log::info!("wrote bindings"),
+        Err(error) => log::error!("couldn't write bindings: {}", error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use amethyst::winit::VirtualKeyCode;
+
+    fn key(key: VirtualKeyCode) -> Button {
+        Button::Key(key)
+    }
+
+    fn bindings() -> Bindings<GameBindings> {
+        let mut bindings = Bindings::<GameBindings>::new();
+        for (axis, pos, neg) in &[
+            (
+                AxisBinding::MoveX(0),
+                VirtualKeyCode::Right,
+                VirtualKeyCode::Left,
+            ),
+            (
+                AxisBinding::MoveY(0),
+                VirtualKeyCode::Up,
+                VirtualKeyCode::Down,
+            ),
+            (
+                AxisBinding::Zoom,
+                VirtualKeyCode::Equals,
+                VirtualKeyCode::Minus,
+            ),
+        ] {
+            bindings
+                .insert_axis(
+                    *axis,
+                    Axis::Emulated {
+                        pos: key(*pos),
+                        neg: key(*neg),
+                    },
+                )
+                .unwrap();
+        }
+        bindings
+            .insert_action_binding(ActionBinding::Pause, vec![key(VirtualKeyCode::P)])
+            .unwrap();
+        for shift in &[VirtualKeyCode::LShift, VirtualKeyCode::RShift] {
+            bindings
+                .insert_action_binding(ActionBinding::FastMovement, vec![key(*shift)])
+                .unwrap();
+        }
+        bindings
+    }
+
+    fn fast_keys(bindings: &Bindings<GameBindings>) -> Vec<Vec<Button>> {
+        bindings
+            .action_bindings(&ActionBinding::FastMovement)
+            .map(|combination| combination.to_vec())
+            .collect()
+    }
+
+    #[test]
+    fn rebinding_to_a_free_key_leaves_the_rest_alone() {
+        let right = BindingSlot::axis(AxisBinding::MoveX(0), true);
+        let up = BindingSlot::axis(AxisBinding::MoveY(0), true);
+
+        let (rebound, conflict) = rebind(&bindings(), &right, key(VirtualKeyCode::D)).unwrap();
+
+        assert_eq!(conflict, None);
+        assert_eq!(right.button(&rebound), Some(key(VirtualKeyCode::D)));
+        assert_eq!(up.button(&rebound), Some(key(VirtualKeyCode::Up)));
+    }
+
+    #[test]
+    fn rebinding_to_a_key_in_use_swaps_them() {
+        let right = BindingSlot::axis(AxisBinding::MoveX(0), true);
+        let up = BindingSlot::axis(AxisBinding::MoveY(0), true);
+
+        let (rebound, conflict) = rebind(&bindings(), &right, key(VirtualKeyCode::Up)).unwrap();
+
+        assert_eq!(conflict, Some(up));
+        assert_eq!(right.button(&rebound), Some(key(VirtualKeyCode::Up)));
+        assert_eq!(up.button(&rebound), Some(key(VirtualKeyCode::Right)));
+    }
+
+    #[test]
+    fn an_action_and_an_axis_swap_too() {
+        let pause = BindingSlot::Action(ActionBinding::Pause);
+        let left = BindingSlot::axis(AxisBinding::MoveX(0), false);
+
+        let (rebound, conflict) = rebind(&bindings(), &pause, key(VirtualKeyCode::Left)).unwrap();
+
+        assert_eq!(conflict, Some(left));
+        assert_eq!(pause.button(&rebound), Some(key(VirtualKeyCode::Left)));
+        assert_eq!(left.button(&rebound), Some(key(VirtualKeyCode::P)));
+    }
+
+    #[test]
+    fn rebinding_keeps_every_key_an_action_has() {
+        let right = BindingSlot::axis(AxisBinding::MoveX(0), true);
+
+        let (rebound, _) = rebind(&bindings(), &right, key(VirtualKeyCode::D)).unwrap();
+
+        assert_eq!(
+            fast_keys(&rebound),
+            vec![
+                vec![key(VirtualKeyCode::LShift)],
+                vec![key(VirtualKeyCode::RShift)]
+            ]
+        );
+    }
+
+    #[test]
+    fn rebinding_an_action_only_swaps_the_key_shown_for_it() {
+        let fast = BindingSlot::Action(ActionBinding::FastMovement);
+
+        let (rebound, _) = rebind(&bindings(), &fast, key(VirtualKeyCode::F)).unwrap();
+
+        assert_eq!(
+            fast_keys(&rebound),
+            vec![
+                vec![key(VirtualKeyCode::F)],
+                vec![key(VirtualKeyCode::RShift)]
+            ]
+        );
+    }
+
+    #[test]
+    fn a_key_in_use_isnt_taken_when_theres_nothing_to_give_back() {
+        let mut unbound = Bindings::<GameBindings>::new();
+        unbound
+            .insert_axis(
+                AxisBinding::MoveX(0),
+                Axis::Emulated {
+                    pos: key(VirtualKeyCode::Right),
+                    neg: key(VirtualKeyCode::Left),
+                },
+            )
+            .unwrap();
+        let pause = BindingSlot::Action(ActionBinding::Pause);
+
+        assert!(matches!(
+            rebind(&unbound, &pause, key(VirtualKeyCode::Left)),
+            Err(RebindError::Taken(BindingSlot::Axis {
+                axis: AxisBinding::MoveX(0),
+                positive: false,
+            }))
+        ));
+    }
+}
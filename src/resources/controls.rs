@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+
+use crate::resources::replay::ReplayRecord;
+
+/// Which way one player wants their ball to roll this frame
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+pub struct Movement {
+    pub move_x: Option<f32>,
+    pub move_y: Option<f32>,
+}
+
+/// What's being asked of the balls and camera this frame. Usually read straight off the keyboard
+/// but a replay can stand in for it
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Controls {
+    /// indexed by player
+    #[serde(default)]
+    pub movement: Vec<Movement>,
+    pub zoom: Option<f32>,
+    pub fast_movement: Option<bool>,
+    /// how long the frame took, anything that moves by the second goes by this so a replay
+    /// times it the same as when it was recorded
+    #[serde(default)]
+    pub delta_seconds: f32,
+}
+
+impl Controls {
+    pub fn movement(&self, player: usize) -> Movement {
+        self.movement.get(player).copied().unwrap_or_default()
+    }
+}
+
+/// Every frame's controls for the game in progress, written out as a replay when the game stops
+#[derive(Debug, Clone, Default)]
+pub struct Recording {
+    pub active: bool,
+    pub replay: ReplayRecord,
+}
+
+/// Recorded controls being fed back in place of the keyboard
+#[derive(Debug, Clone, Default)]
+pub struct Playback {
+    pub frames: Vec<Controls>,
+    pub frame: usize,
+}
+
+impl Playback {
+    pub fn active(&self) -> bool {
+        !self.frames.is_empty()
+    }
+
+    pub fn finished(&self) -> bool {
+        self.frame >= self.frames.len()
+    }
+}
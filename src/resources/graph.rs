@@ -0,0 +1,312 @@
+use crate::components::path::segment_key;
+use crate::resources::save::PathSegmentRecord;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+
+/// A distinct spot on the path, segments laid down on top of one another share a node
+#[derive(Debug, Clone)]
+pub struct SegmentNode {
+    pub key: String,
+    pub x: f32,
+    pub y: f32,
+    pub half_width: f32,
+    pub half_height: f32,
+}
+
+impl SegmentNode {
+    pub fn contains(&self, x: f32, y: f32) -> bool {
+        (x - self.x).abs() <= self.half_width && (y - self.y).abs() <= self.half_height
+    }
+
+    fn distance_to(&self, other: &SegmentNode) -> f32 {
+        ((self.x - other.x).powi(2) + (self.y - other.y).powi(2)).sqrt()
+    }
+}
+
+/// Which segments of the path lead onto which, for anything that needs to find its way around
+#[derive(Debug, Clone, Default)]
+pub struct SegmentGraph {
+    pub nodes: Vec<SegmentNode>,
+    edges: Vec<Vec<usize>>,
+    keys: HashMap<String, usize>,
+    /// pairs of nodes joined by a portal rather than by the path
+    portals: Vec<(usize, usize)>,
+}
+
+impl SegmentGraph {
+    /// Takes the segments in the order the path lays them down, each one leads onto the next.
+    /// Where the path comes back over itself the segments share a node, which is what joins the
+    /// different stretches of it up.
+    pub fn new(segments: Vec<SegmentNode>) -> SegmentGraph {
+        let mut graph = SegmentGraph::default();
+        let mut previous: Option<usize> = None;
+
+        for segment in segments {
+            let node = match graph.keys.get(&segment.key) {
+                Some(node) => *node,
+                None => {
+                    graph.keys.insert(segment.key.clone(), graph.nodes.len());
+                    graph.nodes.push(segment);
+                    graph.edges.push(vec![]);
+                    graph.nodes.len() - 1
+                }
+            };
+
+            if let Some(previous) = previous {
+                graph.add_edge(previous, node);
+            }
+            previous = Some(node);
+        }
+
+        graph
+    }
+
+    pub fn from_segments(path_segments: &[PathSegmentRecord]) -> SegmentGraph {
+        SegmentGraph::new(
+            path_segments
+                .iter()
+                .map(|segment| SegmentNode {
+                    key: segment_key(&segment.transform),
+                    x: segment.transform.translation().x,
+                    y: segment.transform.translation().y,
+                    half_width: segment.rectangle.width * 0.5,
+                    half_height: segment.rectangle.height * 0.5,
+                })
+                .collect(),
+        )
+    }
+
+    /// Joins two segments both ways, does nothing if they're already joined
+    pub fn add_edge(&mut self, a: usize, b: usize) {
+        if a != b && !self.edges[a].contains(&b) {
+            self.edges[a].push(b);
+            self.edges[b].push(a);
+        }
+    }
+
+    /// Joins the segments at either end of a portal, crossing between them costs nothing
+    pub fn add_portal(&mut self, a: &str, b: &str) {
+        if let (Some(a), Some(b)) = (self.node(a), self.node(b)) {
+            if a != b && !self.is_portal(a, b) {
+                self.add_edge(a, b);
+                self.portals.push((a, b));
+            }
+        }
+    }
+
+    pub fn is_portal(&self, a: usize, b: usize) -> bool {
+        self.portals
+            .iter()
+            .any(|(from, to)| (*from == a && *to == b) || (*from == b && *to == a))
+    }
+
+    fn cost(&self, a: usize, b: usize) -> f32 {
+        if self.is_portal(a, b) {
+            0.0
+        } else {
+            self.nodes[a].distance_to(&self.nodes[b])
+        }
+    }
+
+    pub fn neighbours(&self, node: usize) -> &[usize] {
+        &self.edges[node]
+    }
+
+    pub fn node(&self, key: &str) -> Option<usize> {
+        self.keys.get(key).copied()
+    }
+
+    /// The segment a point is on, or the closest one if it's somehow off the path
+    pub fn node_at(&self, x: f32, y: f32) -> Option<usize> {
+        let distance = |node: &SegmentNode| (node.x - x).powi(2) + (node.y - y).powi(2);
+
+        self.nodes
+            .iter()
+            .position(|node| node.contains(x, y))
+            .or_else(|| {
+                self.nodes
+                    .iter()
+                    .enumerate()
+                    .min_by(|(_, a), (_, b)| {
+                        distance(a)
+                            .partial_cmp(&distance(b))
+                            .unwrap_or(Ordering::Equal)
+                    })
+                    .map(|(index, _)| index)
+            })
+    }
+
+    /// The shortest way from one segment to another with A*, both ends included, only going
+    /// through segments that pass the test
+    pub fn find_path(
+        &self,
+        from: usize,
+        to: usize,
+        open: impl Fn(&SegmentNode) -> bool,
+    ) -> Option<Vec<usize>> {
+        // a portal can make somewhere far away closer than it looks, so the guess is the
+        // straight line or the straight line via the best placed portals, whichever's shorter
+        let ends: Vec<usize> = self
+            .portals
+            .iter()
+            .flat_map(|(a, b)| vec![*a, *b])
+            .collect();
+        let from_exit = ends
+            .iter()
+            .map(|end| self.nodes[*end].distance_to(&self.nodes[to]))
+            .fold(f32::INFINITY, f32::min);
+        let heuristic = |node: usize| {
+            let straight = self.nodes[node].distance_to(&self.nodes[to]);
+            let to_entrance = ends
+                .iter()
+                .map(|end| self.nodes[node].distance_to(&self.nodes[*end]))
+                .fold(f32::INFINITY, f32::min);
+            straight.min(to_entrance + from_exit)
+        };
+
+        let mut open = BinaryHeap::new();
+        let mut came_from: HashMap<usize, usize> = HashMap::new();
+        let mut cost: HashMap<usize, f32> = HashMap::new();
+
+        cost.insert(from, 0.0);
+        open.push(Candidate {
+            estimate: heuristic(from),
+            node: from,
+        });
+
+        while let Some(Candidate { node, .. }) = open.pop() {
+            if node == to {
+                let mut path = vec![to];
+                let mut current = to;
+                while let Some(previous) = came_from.get(&current) {
+                    path.push(*previous);
+                    current = *previous;
+                }
+                path.reverse();
+                return Some(path);
+            }
+
+            let node_cost = cost[&node];
+            for neighbour in self.neighbours(node) {
+                if !open(&self.nodes[*neighbour]) {
+                    continue;
+                }
+                let neighbour_cost = node_cost + self.cost(node, *neighbour);
+                if cost
+                    .get(neighbour)
+                    .map_or(true, |existing| neighbour_cost < *existing)
+                {
+                    cost.insert(*neighbour, neighbour_cost);
+                    came_from.insert(*neighbour, node);
+                    open.push(Candidate {
+                        estimate: neighbour_cost + heuristic(*neighbour),
+                        node: *neighbour,
+                    });
+                }
+            }
+        }
+
+        None
+    }
+
+    /// The closest segment, by number of hops, that passes the test
+    pub fn nearest(&self, from: usize, test: impl Fn(&SegmentNode) -> bool) -> Option<usize> {
+        let mut seen = vec![false; self.nodes.len()];
+        let mut queue = VecDeque::new();
+        seen[from] = true;
+        queue.push_back(from);
+
+        while let Some(node) = queue.pop_front() {
+            if test(&self.nodes[node]) {
+                return Some(node);
+            }
+            for neighbour in self.neighbours(node) {
+                if !seen[*neighbour] {
+                    seen[*neighbour] = true;
+                    queue.push_back(*neighbour);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// A node waiting to be looked at, the heap pops the lowest estimate first
+struct Candidate {
+    estimate: f32,
+    node: usize,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .estimate
+            .partial_cmp(&self.estimate)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+/// Segments laid down left to right, one after another, for tests to find their way along
+#[cfg(test)]
+pub(crate) fn line(length: usize) -> SegmentGraph {
+    SegmentGraph::new(
+        (0..length)
+            .map(|i| SegmentNode {
+                key: i.to_string(),
+                x: i as f32 * 24.0,
+                y: 0.0,
+                half_width: 12.0,
+                half_height: 4.0,
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_path_follows_the_path() {
+        let graph = line(5);
+
+        assert_eq!(graph.find_path(0, 4, |_| true), Some(vec![0, 1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn find_path_takes_a_portal_when_its_shorter() {
+        let mut graph = line(5);
+        graph.add_portal("0", "4");
+
+        assert_eq!(graph.find_path(0, 4, |_| true), Some(vec![0, 4]));
+        assert_eq!(graph.find_path(0, 3, |_| true), Some(vec![0, 4, 3]));
+    }
+
+    #[test]
+    fn find_path_goes_round_closed_segments_through_a_portal() {
+        let mut graph = line(5);
+
+        assert_eq!(graph.find_path(0, 4, |node| node.key != "2"), None);
+
+        graph.add_portal("1", "3");
+        assert_eq!(
+            graph.find_path(0, 4, |node| node.key != "2"),
+            Some(vec![0, 1, 3, 4])
+        );
+    }
+}
@@ -0,0 +1,165 @@
+use amethyst::config::Config;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs::read_dir,
+    path::{Path, PathBuf},
+};
+
+/// Anything missing from the other languages is shown in this one
+pub const FALLBACK_LANGUAGE: &str = "en";
+
+/// One language's strings, from a file like `assets/i18n/en.ron`
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct StringTable {
+    /// what the language calls itself, for the settings
+    pub name: String,
+    pub strings: HashMap<String, String>,
+}
+
+/// The strings for whichever language's been picked, looked up by key. Keys look like
+/// "menu.resume", ui prefabs use them as their text and the locale system swaps them for the
+/// real thing.
+#[derive(Debug, Clone, Default)]
+pub struct Locale {
+    pub language: String,
+    pub name: String,
+    dir: PathBuf,
+    strings: HashMap<String, String>,
+    fallback: HashMap<String, String>,
+    /// goes up every time the language changes, so text that's already showing can be redone
+    pub revision: u32,
+}
+
+impl Locale {
+    pub fn load(dir: &Path, language: &str) -> Locale {
+        let mut locale = Locale {
+            dir: dir.to_path_buf(),
+            fallback: read_table(dir, FALLBACK_LANGUAGE)
+                .map(|table| table.strings)
+                .unwrap_or_default(),
+            ..Default::default()
+        };
+        locale.set_language(language);
+        locale
+    }
+
+    pub fn set_language(&mut self, language: &str) {
+        let table = match read_table(&self.dir, language) {
+            Some(table) => table,
+            None if language != FALLBACK_LANGUAGE => {
+                log::error!("no strings for {}, using {}", language, FALLBACK_LANGUAGE);
+                return self.set_language(FALLBACK_LANGUAGE);
+            }
+            None => StringTable::default(),
+        };
+
+        self.language = language.to_string();
+        self.name = table.name;
+        self.strings = table.strings;
+        self.revision += 1;
+    }
+
+    /// Every language there are strings for, by file name
+    pub fn languages(&self) -> Vec<String> {
+        read_languages(&self.dir)
+    }
+
+    pub fn has(&self, key: &str) -> bool {
+        self.strings.contains_key(key) || self.fallback.contains_key(key)
+    }
+
+    /// The string for a key, or the english one if it hasn't been translated, or the key itself
+    /// if there isn't even that
+    pub fn text(&self, key: &str) -> String {
+        self.strings
+            .get(key)
+            .or_else(|| self.fallback.get(key))
+            .cloned()
+            .unwrap_or_else(|| key.to_string())
+    }
+
+    /// Like `text`, with "{0}", "{1}" and so on filled in from the arguments. They're numbered
+    /// so a translation can put them in whatever order it needs.
+    pub fn format(&self, key: &str, args: &[&dyn Display]) -> String {
+        args.iter()
+            .enumerate()
+            .fold(self.text(key), |text, (index, arg)| {
+                text.replace(&format!("{{{}}}", index), &arg.to_string())
+            })
+    }
+}
+
+fn read_languages(dir: &Path) -> Vec<String> {
+    let mut languages: Vec<String> = read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter_map(|entry| {
+                    let path = entry.path();
+                    if path.extension()? != "ron" {
+                        return None;
+                    }
+                    Some(path.file_stem()?.to_str()?.to_string())
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    languages.sort();
+    languages
+}
+
+fn read_table(dir: &Path, language: &str) -> Option<StringTable> {
+    match StringTable::load(dir.join(format!("{}.ron", language))) {
+        Ok(table) => Some(table),
+        Err(error) => {
+            log::error!("couldn't read the strings for {}: {}", language, error);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(key, text)| (key.to_string(), text.to_string()))
+            .collect()
+    }
+
+    /// French with only the menu translated, over english with everything
+    fn locale() -> Locale {
+        Locale {
+            language: "fr".to_string(),
+            name: "Français".to_string(),
+            strings: strings(&[("menu.resume", "Reprendre")]),
+            fallback: strings(&[
+                ("menu.resume", "Resume"),
+                ("load_game.details", "{1} steps, {0} explored"),
+            ]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn format_uses_the_translation_when_there_is_one() {
+        assert_eq!(locale().format("menu.resume", &[]), "Reprendre");
+    }
+
+    #[test]
+    fn format_falls_back_to_english_and_fills_it_in() {
+        assert_eq!(
+            locale().format("load_game.details", &[&"12%", &30]),
+            "30 steps, 12% explored"
+        );
+    }
+
+    #[test]
+    fn format_shows_the_key_when_nothing_has_it() {
+        assert_eq!(locale().format("menu.missing", &[&1]), "menu.missing");
+    }
+}
@@ -1 +1,12 @@
+pub mod bindings;
+pub mod controls;
+pub mod graph;
+pub mod locale;
+pub mod pause;
+pub mod preferences;
+pub mod records;
+pub mod replay;
+pub mod route;
 pub mod save;
+pub mod saves;
+pub mod session;
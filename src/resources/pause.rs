@@ -0,0 +1,5 @@
+/// Set while something is covering the game, like the menu, so clocks can stop ticking
+#[derive(Debug, Clone, Default)]
+pub struct Pause {
+    pub paused: bool,
+}
@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{create_dir, File},
+    io::{Read, Write},
+    path::Path,
+};
+
+use crate::components::videographer::CameraMode;
+use crate::config::WanderballConfig;
+
+const PREFERENCES_FILE: &str = "wanderball-preferences.json";
+
+/// Whatever's been changed in the settings menu, anything left unset comes from
+/// config/wanderball.ron or config/display.ron
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Preferences {
+    pub music_volume: Option<f32>,
+    pub camera_mode: Option<CameraMode>,
+    pub zoom_factor: Option<f32>,
+    pub show_hud: Option<bool>,
+    pub window_size: Option<(u32, u32)>,
+    pub language: Option<String>,
+}
+
+impl Preferences {
+    /// Lays the preferences over the config, the window size is left to the display config
+    pub fn apply(&self, config: &mut WanderballConfig) {
+        if let Some(music_volume) = self.music_volume {
+            config.music_volume = music_volume;
+        }
+        if let Some(camera_mode) = self.camera_mode {
+            config.camera_mode = camera_mode;
+        }
+        if let Some(zoom_factor) = self.zoom_factor {
+            config.zoom_factor = zoom_factor;
+        }
+        if let Some(show_hud) = self.show_hud {
+            config.show_hud = show_hud;
+        }
+    }
+}
+
+pub fn read_preferences() -> Preferences {
+    let preferences_file_path = Path::new(".save").join(PREFERENCES_FILE);
+
+    let mut contents = String::new();
+    if let Ok(mut f) = File::open(preferences_file_path) {
+        let _ = f.read_to_string(&mut contents);
+    }
+
+    serde_json::from_str(&contents).unwrap_or_default()
+}
+
+pub fn write_preferences(preferences: &Preferences) {
+    let save_dir = Path::new(".save");
+    if !save_dir.exists() && create_dir(save_dir).is_err() {
+        log::error!("couldn't create the save directory for preferences");
+        return;
+    }
+
+    if let Ok(record) = serde_json::to_string(preferences) {
+        if let Ok(mut f) = File::create(save_dir.join(PREFERENCES_FILE)) {
+            let _ = f.write_all(record.as_bytes());
+            log::info!("wrote preferences");
+        }
+    }
+}
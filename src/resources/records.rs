@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs::{create_dir, File},
+    io::{Read, Write},
+    path::Path,
+};
+
+const RECORDS_FILE: &str = "wanderball-records.json";
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct PersonalBestRecord {
+    pub time: f32,
+    pub splits: Vec<f32>,
+}
+
+/// Best times kept across games, keyed by the seed the path was generated from
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Records {
+    pub personal_bests: HashMap<String, PersonalBestRecord>,
+}
+
+pub fn read_records() -> Records {
+    let records_file_path = Path::new(".save").join(RECORDS_FILE);
+
+    let mut contents = String::new();
+    if let Ok(mut f) = File::open(records_file_path) {
+        let _ = f.read_to_string(&mut contents);
+    }
+
+    serde_json::from_str(&contents).unwrap_or_default()
+}
+
+pub fn write_records(records: &Records) {
+    let save_dir = Path::new(".save");
+    if !save_dir.exists() && create_dir(save_dir).is_err() {
+        log::error!("couldn't create the save directory for records");
+        return;
+    }
+
+    if let Ok(record) = serde_json::to_string(records) {
+        if let Ok(mut f) = File::create(save_dir.join(RECORDS_FILE)) {
+            let _ = f.write_all(record.as_bytes());
+            log::info!("wrote records");
+        }
+    }
+}
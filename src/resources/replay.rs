@@ -0,0 +1,91 @@
+use crate::resources::controls::Controls;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{create_dir_all, read_dir, File},
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Goes up whenever what's recorded changes shape, replays from another version won't play back
+/// the same so they aren't played at all
+pub const REPLAY_VERSION: u32 = 1;
+
+/// Everything it takes to play a game back exactly: the level to generate and the controls for
+/// each frame. The ball moves a fixed amount per frame, and each frame's controls carry how long
+/// it took for anything that moves by the second, so frames are all that need replaying. Where
+/// the ball ended up each frame comes along too, for ghosts
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ReplayRecord {
+    /// replays from before there was a version are 0
+    #[serde(default)]
+    pub version: u32,
+    pub level: u32,
+    pub seed: u64,
+    pub path_length: usize,
+    pub frames: Vec<Controls>,
+    #[serde(default)]
+    pub positions: Vec<[f32; 2]>,
+}
+
+pub fn replay_dir() -> PathBuf {
+    Path::new(".save").join("replays")
+}
+
+pub fn write_replay(replay: &ReplayRecord) {
+    let replay_dir = replay_dir();
+    if create_dir_all(&replay_dir).is_err() {
+        log::error!("couldn't create the replay directory");
+        return;
+    }
+
+    let recorded_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    let replay_file_path = replay_dir.join(format!("{}-{}.json", replay.seed, recorded_at));
+
+    if let Ok(record) = serde_json::to_string(replay) {
+        if let Ok(mut f) = File::create(&replay_file_path) {
+            let _ = f.write_all(record.as_bytes());
+            log::info!("wrote replay {}", replay_file_path.display());
+        }
+    }
+}
+
+/// A replay by its file name in the replay directory
+pub fn named_replay(name: &str) -> Option<ReplayRecord> {
+    read_replay(&replay_dir().join(name))
+}
+
+pub fn read_replay(replay_file_path: &Path) -> Option<ReplayRecord> {
+    let mut f = File::open(replay_file_path).ok()?;
+    let mut contents = String::new();
+    f.read_to_string(&mut contents).ok()?;
+    let replay: ReplayRecord = serde_json::from_str(&contents).ok()?;
+
+    if replay.version != REPLAY_VERSION {
+        log::error!(
+            "{} was recorded by replay version {}, this is version {}",
+            replay_file_path.display(),
+            replay.version,
+            REPLAY_VERSION
+        );
+        return None;
+    }
+    Some(replay)
+}
+
+/// The most recently recorded replay, if there are any
+pub fn latest_replay() -> Option<ReplayRecord> {
+    let latest = read_dir(replay_dir())
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            Some((modified, entry.path()))
+        })
+        .max_by_key(|(modified, _)| *modified)?;
+
+    read_replay(&latest.1)
+}
@@ -0,0 +1,175 @@
+use crate::resources::{
+    controls::Movement,
+    graph::{SegmentGraph, SegmentNode},
+};
+
+/// How close the ball has to get to where was clicked to count as there
+const ARRIVED: f32 = 1.0;
+/// Frames without moving before the route's given up on, a locked gate or a corner the ball
+/// can't get round
+const STUCK_FRAMES: usize = 30;
+
+/// Where the first player's ball is rolling to after a click on the path
+#[derive(Debug, Clone, Default)]
+pub struct Route {
+    /// segment keys still to roll through, the clicked one last
+    pub segments: Vec<String>,
+    /// the spot that was clicked
+    pub target: Option<(f32, f32)>,
+    /// the mouse button was already down last frame, holding it down is only one click
+    pub clicking: bool,
+    last_position: Option<(f32, f32)>,
+    still_frames: usize,
+}
+
+impl Route {
+    pub fn active(&self) -> bool {
+        self.target.is_some()
+    }
+
+    pub fn clear(&mut self) {
+        *self = Route {
+            clicking: self.clicking,
+            ..Default::default()
+        };
+    }
+
+    /// Plans the shortest way over the segment graph from the segment the ball's on to the
+    /// clicked one, only through segments that are open, anything already planned is dropped
+    /// even if there's no way there
+    pub fn plan(
+        &mut self,
+        graph: &SegmentGraph,
+        from: (f32, f32),
+        to: (f32, f32),
+        open: impl Fn(&SegmentNode) -> bool,
+    ) -> bool {
+        self.clear();
+
+        let start = graph.node_at(from.0, from.1);
+        let end = graph
+            .nodes
+            .iter()
+            .position(|node| node.contains(to.0, to.1));
+        let path = match (start, end) {
+            (Some(start), Some(end)) => graph.find_path(start, end, open),
+            _ => None,
+        };
+
+        match path {
+            Some(path) => {
+                self.segments = path
+                    .into_iter()
+                    .skip(1)
+                    .map(|node| graph.nodes[node].key.clone())
+                    .collect();
+                self.target = Some(to);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Which way to push the ball this frame to carry on along the route, heading for the
+    /// middle of each segment in turn and then for the clicked spot
+    pub fn steer(&mut self, graph: &SegmentGraph, x: f32, y: f32) -> Movement {
+        let target = match self.target {
+            Some(target) => target,
+            None => return Movement::default(),
+        };
+
+        if self.last_position == Some((x, y)) {
+            self.still_frames += 1;
+        } else {
+            self.still_frames = 0;
+        }
+        self.last_position = Some((x, y));
+        if self.still_frames >= STUCK_FRAMES {
+            log::info!("gave up on the route to ({}, {})", target.0, target.1);
+            self.clear();
+            return Movement::default();
+        }
+
+        // anything up to the segment the ball's on is done with, a portal can skip it ahead
+        let here = graph.node_at(x, y);
+        if let Some(reached) = here.and_then(|here| {
+            self.segments
+                .iter()
+                .position(|key| *key == graph.nodes[here].key)
+        }) {
+            self.segments.drain(..=reached);
+        }
+
+        let (goal_x, goal_y) = match (here, self.segments.first().and_then(|key| graph.node(key))) {
+            // going through a portal means rolling into the middle of this end of it
+            (Some(here), Some(next)) if graph.is_portal(here, next) => {
+                (graph.nodes[here].x, graph.nodes[here].y)
+            }
+            (_, Some(next)) => (graph.nodes[next].x, graph.nodes[next].y),
+            (_, None) => target,
+        };
+
+        let (dx, dy) = (goal_x - x, goal_y - y);
+        if self.segments.is_empty() && dx.abs() <= ARRIVED && dy.abs() <= ARRIVED {
+            self.clear();
+            return Movement::default();
+        }
+
+        Movement {
+            move_x: Some(dx.clamp(-1.0, 1.0)),
+            move_y: Some(dy.clamp(-1.0, 1.0)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::graph::line;
+
+    #[test]
+    fn plan_lists_the_segments_after_the_one_the_ball_is_on() {
+        let graph = line(5);
+        let mut route = Route::default();
+
+        assert!(route.plan(&graph, (0.0, 0.0), (96.0, 0.0), |_| true));
+        assert_eq!(route.segments, vec!["1", "2", "3", "4"]);
+        assert_eq!(route.target, Some((96.0, 0.0)));
+    }
+
+    #[test]
+    fn plan_gives_up_when_the_way_is_shut() {
+        let graph = line(5);
+        let mut route = Route::default();
+
+        assert!(!route.plan(&graph, (0.0, 0.0), (96.0, 0.0), |node| node.key != "2"));
+        assert!(!route.active());
+        assert!(route.segments.is_empty());
+    }
+
+    #[test]
+    fn steer_heads_for_the_next_segment() {
+        let graph = line(5);
+        let mut route = Route::default();
+        route.plan(&graph, (0.0, 0.0), (96.0, 0.0), |_| true);
+
+        assert_eq!(
+            route.steer(&graph, 0.0, 0.0),
+            Movement {
+                move_x: Some(1.0),
+                move_y: Some(0.0),
+            }
+        );
+        assert!(route.active());
+    }
+
+    #[test]
+    fn steer_stops_once_the_ball_gets_there() {
+        let graph = line(5);
+        let mut route = Route::default();
+        route.plan(&graph, (0.0, 0.0), (96.0, 0.0), |_| true);
+
+        assert_eq!(route.steer(&graph, 96.0, 0.0), Movement::default());
+        assert!(!route.active());
+    }
+}
@@ -1,5 +1,12 @@
 use crate::components::{
+    collectible::Collectible,
+    gate::{Inventory, Key, KeyColor},
+    hazard::{Hazard, LastCheckpoint},
+    npc::Npc,
+    player::Player,
+    portal::PortalPair,
     shapes::{circle::Circle, rectangle::Rectangle},
+    surface::Surface,
     videographer::Videographer,
 };
 
@@ -12,12 +19,24 @@ pub struct PathSegmentRecord {
     pub transform: Transform,
     pub rectangle: Rectangle,
     pub tint: Tint,
+    #[serde(default)]
+    pub surface: Surface,
+    #[serde(default)]
+    pub checkpoint: bool,
+    #[serde(default)]
+    pub gate: Option<KeyColor>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct BallRecord {
     pub transform: Transform,
     pub circle: Circle,
+    #[serde(default)]
+    pub player: Option<Player>,
+    #[serde(default)]
+    pub pedometer: Option<PedometerRecord>,
+    #[serde(default)]
+    pub checkpoint: Option<LastCheckpoint>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Default, Clone)]
@@ -39,10 +58,71 @@ pub struct GameRecord {
     pub videographer: VideographerRecord,
     pub camera: CameraRecord,
     pub pedometer: PedometerRecord,
+    #[serde(default)]
+    pub fog: FogOfWarRecord,
+    #[serde(default)]
+    pub collectibles: Vec<CollectibleRecord>,
+    #[serde(default)]
+    pub level: Option<LevelRecord>,
+    #[serde(default)]
+    pub npcs: Vec<NpcRecord>,
+    #[serde(default)]
+    pub hazards: Vec<HazardRecord>,
+    #[serde(default)]
+    pub portals: Vec<PortalPair>,
+    #[serde(default)]
+    pub keys: Vec<KeyRecord>,
+    #[serde(default)]
+    pub inventory: Inventory,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct PedometerRecord {
     pub steps: i32,
     pub visited: HashMap<String, ()>,
+    #[serde(default)]
+    pub distance: f32,
+    #[serde(default)]
+    pub time_played: f32,
+    #[serde(default)]
+    pub longest_streak: i32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct FogOfWarRecord {
+    pub revealed: HashMap<String, ()>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CollectibleRecord {
+    pub transform: Transform,
+    pub collectible: Collectible,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LevelRecord {
+    pub number: u32,
+    pub seed: u64,
+    pub path_length: usize,
+    pub goal: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct NpcRecord {
+    pub transform: Transform,
+    pub circle: Circle,
+    pub npc: Npc,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HazardRecord {
+    pub transform: Transform,
+    pub rectangle: Rectangle,
+    pub hazard: Hazard,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct KeyRecord {
+    pub transform: Transform,
+    pub key: Key,
 }
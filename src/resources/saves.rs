@@ -0,0 +1,254 @@
+use crate::components::path::unique_segment_count;
+use crate::resources::locale::Locale;
+use crate::resources::save::GameRecord;
+use std::{
+    fs::{create_dir_all, read_dir, remove_file, rename, File},
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+/// Where the one save lived before there could be more than one
+const OLD_SAVE_FILE: &str = "wanderball-save.json";
+const LONGEST_NAME: usize = 20;
+
+/// One saved game, with what the load screen shows about it
+#[derive(Debug, Clone)]
+pub struct SaveSlot {
+    pub name: String,
+    pub path: PathBuf,
+    /// seconds since the unix epoch
+    pub saved_at: u64,
+    pub steps: i32,
+    pub exploration: f32,
+}
+
+impl SaveSlot {
+    /// When it was saved, in UTC since that's all the timestamp knows about
+    pub fn saved_date(&self) -> String {
+        time::OffsetDateTime::from_unix_timestamp(self.saved_at as i64).format("%Y-%m-%d %H:%M UTC")
+    }
+
+    pub fn details(&self, locale: &Locale) -> String {
+        locale.format(
+            "load_game.details",
+            &[
+                &self.saved_date(),
+                &self.steps,
+                &format!("{:.1}", self.exploration),
+            ],
+        )
+    }
+}
+
+/// Which save the game in progress came from, or was last saved to. Saving again writes over it.
+#[derive(Debug, Clone, Default)]
+pub struct CurrentSave {
+    pub name: Option<String>,
+}
+
+/// Set as soon as anything happens in the game, and cleared again by saving it. Leaving the game
+/// only asks first when it's set.
+#[derive(Debug, Clone, Default)]
+pub struct UnsavedChanges {
+    pub dirty: bool,
+}
+
+/// Why a save couldn't be renamed
+#[derive(Debug, Clone)]
+pub enum RenameError {
+    NoName,
+    /// another save already has the name
+    Taken(String),
+    Failed(String),
+}
+
+pub fn save_dir() -> PathBuf {
+    Path::new(".save").join("saves")
+}
+
+fn save_path(name: &str) -> PathBuf {
+    save_dir().join(format!("{}.json", name))
+}
+
+/// Moves a save from before there were slots in with the rest, once when the game starts
+pub fn migrate_old_save() {
+    let old_save = Path::new(".save").join(OLD_SAVE_FILE);
+    if !old_save.exists() {
+        return;
+    }
+    if create_dir_all(save_dir()).is_err() {
+        log::error!("couldn't create the saves directory");
+        return;
+    }
+
+    let name = next_save_name();
+    match rename(&old_save, save_path(&name)) {
+        Ok(()) => log::info!("moved the old save into the saves directory as {}", name),
+        Err(error) => log::error!(
+            "couldn't move the old save into the saves directory: {}",
+            error
+        ),
+    }
+}
+
+/// Every save there is, the most recent first
+pub fn list_saves() -> Vec<SaveSlot> {
+    let entries = match read_dir(save_dir()) {
+        Ok(entries) => entries,
+        Err(_) => return vec![],
+    };
+
+    let mut saves: Vec<SaveSlot> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let path = entry.path();
+            let name = path.file_stem()?.to_str()?.to_string();
+            let saved_at = entry
+                .metadata()
+                .ok()?
+                .modified()
+                .ok()?
+                .duration_since(UNIX_EPOCH)
+                .ok()?
+                .as_secs();
+            let record = match read_save(&path) {
+                Some(record) => record,
+                None => {
+                    log::error!("couldn't read the save {}", path.display());
+                    return None;
+                }
+            };
+            let segment_count = unique_segment_count(&record.path_segments);
+            let exploration = if segment_count == 0 {
+                0.0
+            } else {
+                (record.pedometer.visited.len() as f32 / segment_count as f32 * 100.0).min(100.0)
+            };
+
+            Some(SaveSlot {
+                name,
+                path,
+                saved_at,
+                steps: record.pedometer.steps,
+                exploration,
+            })
+        })
+        .collect();
+
+    saves.sort_by(|a, b| b.saved_at.cmp(&a.saved_at).then(a.name.cmp(&b.name)));
+    saves
+}
+
+pub fn read_save(save_file_path: &Path) -> Option<GameRecord> {
+    let mut f = File::open(save_file_path).ok()?;
+    let mut contents = String::new();
+    f.read_to_string(&mut contents).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+/// Whether the save made it to disk
+pub fn write_save(name: &str, game_record: &GameRecord) -> bool {
+    if create_dir_all(save_dir()).is_err() {
+        log::error!("couldn't create the saves directory");
+        return false;
+    }
+
+    let written = serde_json::to_string(game_record)
+        .ok()
+        .and_then(|record| {
+            let mut f = File::create(save_path(name)).ok()?;
+            f.write_all(record.as_bytes()).ok()
+        })
+        .is_some();
+    if written {
+        log::info!("wrote save {}", name);
+    } else {
+        log::error!("couldn't write save {}", name);
+    }
+    written
+}
+
+pub fn delete_save(save: &SaveSlot) {
+    match remove_file(&save.path) {
+        Ok(()) => log::info!("deleted save {}", save.name),
+        Err(error) => log::error!("couldn't delete save {}: {}", save.name, error),
+    }
+}
+
+/// Gives a save a new name, names end up in file names so they're kept to letters, numbers,
+/// spaces, dashes and underscores
+pub fn rename_save(save: &SaveSlot, name: &str) -> Result<String, RenameError> {
+    let name: String = name
+        .trim()
+        .chars()
+        .filter(|c| c.is_alphanumeric() || *c == ' ' || *c == '-' || *c == '_')
+        .take(LONGEST_NAME)
+        .collect();
+    let name = name.trim().to_string();
+
+    if name.is_empty() {
+        return Err(RenameError::NoName);
+    }
+    if name == save.name {
+        return Ok(name);
+    }
+    if save_path(&name).exists() {
+        return Err(RenameError::Taken(name));
+    }
+
+    rename(&save.path, save_path(&name)).map_err(|error| RenameError::Failed(error.to_string()))?;
+    log::info!("renamed save {} to {}", save.name, name);
+    Ok(name)
+}
+
+/// The first of "save 1", "save 2" and so on that isn't taken yet
+pub fn next_save_name() -> String {
+    (1..)
+        .map(|number| format!("save {}", number))
+        .find(|name| !save_path(name).exists())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A save already called `name`, so renaming it to the same thing never touches the disk
+    fn save(name: &str) -> SaveSlot {
+        SaveSlot {
+            name: name.to_string(),
+            path: save_path(name),
+            saved_at: 0,
+            steps: 0,
+            exploration: 0.0,
+        }
+    }
+
+    #[test]
+    fn rename_save_drops_anything_that_cant_go_in_a_file_name() {
+        assert_eq!(
+            rename_save(&save("my save"), " my/ save?! ").unwrap(),
+            "my save"
+        );
+    }
+
+    #[test]
+    fn rename_save_keeps_names_short() {
+        let name = "a very long save nam";
+
+        assert_eq!(name.len(), LONGEST_NAME);
+        assert_eq!(
+            rename_save(&save(name), "a very long save name indeed").unwrap(),
+            name
+        );
+    }
+
+    #[test]
+    fn rename_save_needs_something_left_to_call_it() {
+        assert!(matches!(
+            rename_save(&save("my save"), " ?!/* "),
+            Err(RenameError::NoName)
+        ));
+    }
+}
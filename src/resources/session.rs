@@ -0,0 +1,66 @@
+use crate::components::wanderdata::Pedometer;
+use std::collections::HashSet;
+
+/// How far the ball has to roll before another point goes on the route
+const ROUTE_SPACING: f32 = 5.0;
+/// Past this the route keeps every other point, it only needs to be good enough for a small map
+const LONGEST_ROUTE: usize = 2000;
+
+/// Where the pedometer was when the game started or was loaded, and where the first player's
+/// ball has been since, so the session summary can tell what happened this time round
+#[derive(Debug, Clone, Default)]
+pub struct Session {
+    pub steps: i32,
+    pub distance: f32,
+    pub time_played: f32,
+    /// segments that had already been visited
+    pub visited: HashSet<String>,
+    pub route: Vec<(f32, f32)>,
+}
+
+impl Session {
+    pub fn start(pedometer: &Pedometer) -> Session {
+        Session {
+            steps: pedometer.steps,
+            distance: pedometer.distance,
+            time_played: pedometer.time_played,
+            visited: pedometer.visited.keys().cloned().collect(),
+            route: vec![],
+        }
+    }
+
+    pub fn track(&mut self, x: f32, y: f32) {
+        let far_enough = self.route.last().map_or(true, |(last_x, last_y)| {
+            (x - last_x).hypot(y - last_y) >= ROUTE_SPACING
+        });
+        if !far_enough {
+            return;
+        }
+
+        if self.route.len() >= LONGEST_ROUTE {
+            self.route = self.route.iter().step_by(2).copied().collect();
+        }
+        self.route.push((x, y));
+    }
+
+    pub fn steps(&self, pedometer: &Pedometer) -> i32 {
+        pedometer.steps - self.steps
+    }
+
+    pub fn distance(&self, pedometer: &Pedometer) -> f32 {
+        pedometer.distance - self.distance
+    }
+
+    pub fn time_played(&self, pedometer: &Pedometer) -> f32 {
+        pedometer.time_played - self.time_played
+    }
+
+    /// Segments no one had been on before this session
+    pub fn discovered(&self, pedometer: &Pedometer) -> usize {
+        pedometer
+            .visited
+            .keys()
+            .filter(|segment| !self.visited.contains(*segment))
+            .count()
+    }
+}
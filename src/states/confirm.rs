@@ -0,0 +1,148 @@
+use amethyst::{
+    ecs::prelude::Entity,
+    input::{is_close_requested, is_key_down},
+    prelude::*,
+    ui::{UiCreator, UiEvent, UiEventType, UiFinder, UiText},
+    winit::VirtualKeyCode,
+};
+
+use crate::states::navigation::MenuNavigation;
+use crate::states::{GameStateEvent, GameTrans};
+
+const LABEL_QUESTION: &str = "confirm_question";
+const BUTTON_YES: &str = "confirm_yes";
+const BUTTON_NO: &str = "confirm_no";
+
+/// The answer to a confirm dialog, left behind for the state that pushed it to pick up with
+/// `take_choice` once it's back on top
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Choice {
+    /// whatever the asking state called the question, so it knows which one this answers
+    pub id: &'static str,
+    pub confirmed: bool,
+}
+
+/// The last dialog's answer, if nobody's picked it up yet
+pub fn take_choice(world: &mut World) -> Option<Choice> {
+    world
+        .try_fetch_mut::<Option<Choice>>()
+        .and_then(|mut choice| choice.take())
+}
+
+/// Asks a yes or no question over whatever pushed it, enter is yes unless a button has been picked
+/// out with the arrow keys, and escape is no
+#[derive(Debug)]
+pub struct Confirm {
+    id: &'static str,
+    question: String,
+    ui_root: Option<Entity>,
+    navigation: MenuNavigation,
+    label_question: Option<Entity>,
+    button_yes: Option<Entity>,
+    button_no: Option<Entity>,
+}
+
+impl Confirm {
+    pub fn new(id: &'static str, question: &str) -> Confirm {
+        Confirm {
+            id,
+            question: question.to_string(),
+            ui_root: None,
+            navigation: MenuNavigation::default(),
+            label_question: None,
+            button_yes: None,
+            button_no: None,
+        }
+    }
+
+    fn answer(&self, world: &mut World, confirmed: bool) -> GameTrans {
+        log::info!("[Trans::Pop] {} confirmed: {}", self.id, confirmed);
+        world.insert(Some(Choice {
+            id: self.id,
+            confirmed,
+        }));
+        Trans::Pop
+    }
+}
+
+impl State<GameData<'static, 'static>, GameStateEvent> for Confirm {
+    fn on_start(&mut self, state_data: StateData<'_, GameData<'_, '_>>) {
+        let StateData { world, .. } = state_data;
+
+        // an answer nobody picked up shouldn't be taken for this one
+        world.insert(None::<Choice>);
+        self.ui_root =
+            Some(world.exec(|mut creator: UiCreator<'_>| creator.create("ui/confirm.ron", ())));
+    }
+
+    fn update(&mut self, state_data: StateData<'_, GameData<'_, '_>>) -> GameTrans {
+        let StateData { world, data } = state_data;
+        data.update(world);
+        self.navigation.find(world, self.ui_root);
+
+        if self.label_question.is_none() || self.button_yes.is_none() || self.button_no.is_none() {
+            world.exec(|ui_finder: UiFinder<'_>| {
+                self.label_question = ui_finder.find(LABEL_QUESTION);
+                self.button_yes = ui_finder.find(BUTTON_YES);
+                self.button_no = ui_finder.find(BUTTON_NO);
+            });
+        }
+
+        let mut ui_text = world.write_storage::<UiText>();
+        if let Some(text) = self.label_question.and_then(|label| ui_text.get_mut(label)) {
+            text.text = self.question.clone();
+        }
+
+        Trans::None
+    }
+
+    fn handle_event(
+        &mut self,
+        state_data: StateData<'_, GameData<'_, '_>>,
+        event: GameStateEvent,
+    ) -> GameTrans {
+        let StateData { world, .. } = state_data;
+
+        match event {
+            StateEvent::Window(event) => {
+                // enter only answers once a button's highlighted, so it can't say yes by accident
+                if self.navigation.handle_event(world, &event) {
+                    Trans::None
+                } else if is_close_requested(&event) {
+                    Trans::Quit
+                } else if is_key_down(&event, VirtualKeyCode::Escape) {
+                    self.answer(world, false)
+                } else {
+                    Trans::None
+                }
+            }
+            StateEvent::Ui(UiEvent {
+                event_type: UiEventType::Click,
+                target,
+            }) => {
+                if Some(target) == self.button_yes {
+                    self.answer(world, true)
+                } else if Some(target) == self.button_no {
+                    self.answer(world, false)
+                } else {
+                    Trans::None
+                }
+            }
+            _ => Trans::None,
+        }
+    }
+
+    fn on_stop(&mut self, data: StateData<GameData>) {
+        if let Some(root_entity) = self.ui_root {
+            data.world
+                .delete_entity(root_entity)
+                .expect("failed to close confirm dialog");
+        }
+
+        self.ui_root = None;
+        self.navigation = MenuNavigation::default();
+        self.label_question = None;
+        self.button_yes = None;
+        self.button_no = None;
+    }
+}
@@ -1,30 +1,85 @@
 use crate::audio::start_audio;
+use crate::autopilot::Autopilot;
 use crate::camera::{initialize_camera, load_camera};
 use crate::components::ball::{initialize_ball, load_ball, Ball};
+use crate::components::collectible::{
+    init_collection, initialize_collectibles, load_collectibles, Collectible,
+};
+use crate::components::fog::{initialize_fog, load_fog};
+use crate::components::gate::{load_inventory, load_keys, Gate, Inventory, Key};
+use crate::components::ghost::initialize_ghost;
+use crate::components::hazard::{
+    initialize_hazards, load_hazards, Checkpoint, Hazard, LastCheckpoint,
+};
+use crate::components::hud::{init_hud, HudWidget};
+use crate::components::level::{initialize_level, load_level, Level};
+use crate::components::npc::{init_segment_graph, initialize_npcs, load_npcs, Npc};
 use crate::components::path::{initialize_path, load_path, PathSegment};
+use crate::components::player::{Player, Race};
+use crate::components::portal::{load_portals, Portal, PortalCooldown};
 use crate::components::shapes::{circle::Circle, rectangle::Rectangle};
+use crate::components::speedrun::init_speedrun;
+use crate::components::surface::Surface;
 use crate::components::videographer::{initialize_videographer, load_videographer, Videographer};
-use crate::components::wanderdata::{init_coordinates, init_pedometer, load_pedometer, Pedometer};
+use crate::components::wanderdata::{init_pedometer, load_pedometer, Pedometer};
+use crate::config::WanderballConfig;
+use crate::resources::bindings::ActionBinding;
+use crate::resources::controls::{Controls, Playback, Recording};
+use crate::resources::pause::Pause;
+use crate::resources::replay::{write_replay, ReplayRecord, REPLAY_VERSION};
+use crate::resources::route::Route;
 use crate::resources::save::GameRecord;
+use crate::resources::saves::{CurrentSave, UnsavedChanges};
+use crate::resources::session::Session;
 use crate::spritesheet;
+use crate::states::level_complete::LevelComplete;
 use crate::states::menu::Menu;
+use crate::states::summary::Summary;
+use crate::states::{GameStateEvent, GameTrans};
 use amethyst::{
-    input::{is_close_requested, is_key_down},
+    input::{is_close_requested, is_key_down, InputEvent},
     prelude::*,
     renderer::Camera,
     winit::VirtualKeyCode,
 };
 
 #[derive(Default)]
-pub struct Wanderball;
+pub struct Wanderball {
+    level: Option<Level>,
+    /// the level complete screen only comes up the once, carrying on wandering afterwards
+    /// doesn't bring it back
+    completion_shown: bool,
+}
+
+impl Wanderball {
+    /// Start a new game on a specific level rather than the first one
+    pub fn at_level(level: Level) -> Wanderball {
+        Wanderball {
+            level: Some(level),
+            ..Default::default()
+        }
+    }
+}
 
-impl SimpleState for Wanderball {
+impl State<GameData<'static, 'static>, GameStateEvent> for Wanderball {
     fn on_start(&mut self, state_data: StateData<'_, GameData<'_, '_>>) {
         let StateData { world, .. } = state_data;
 
         world.register::<Circle>();
         world.register::<Rectangle>();
         world.register::<Pedometer>();
+        world.register::<Collectible>();
+        world.register::<Player>();
+        world.register::<Npc>();
+        world.register::<Surface>();
+        world.register::<Hazard>();
+        world.register::<Checkpoint>();
+        world.register::<LastCheckpoint>();
+        world.register::<Portal>();
+        world.register::<PortalCooldown>();
+        world.register::<Gate>();
+        world.register::<Key>();
+        world.register::<HudWidget>();
 
         let sprite_sheet_handle = spritesheet::load_sprite_sheet(world);
 
@@ -37,34 +92,101 @@ impl SimpleState for Wanderball {
         // try to load, if we can't... start a new game?
         // should eventually report and prompt for what to do if this happens
         let videographer;
+        let loaded = game_record.is_some();
         if let Some(record) = game_record {
+            load_level(world, record.level);
+            load_portals(world, record.portals);
             load_path(world, record.path_segments, &sprite_sheet_handle);
-            load_ball(world, record.balls, &sprite_sheet_handle);
+            load_ball(world, record.balls, &record.pedometer, &sprite_sheet_handle);
             videographer = load_videographer(world, record.videographer);
             load_camera(world, record.camera, videographer);
             load_pedometer(world, record.pedometer);
+            load_fog(world, record.fog);
+            load_collectibles(world, record.collectibles, &sprite_sheet_handle);
+            init_segment_graph(world);
+            load_npcs(world, record.npcs, &sprite_sheet_handle);
+            load_hazards(world, record.hazards, &sprite_sheet_handle);
+            load_keys(world, record.keys, &sprite_sheet_handle);
+            load_inventory(world, record.inventory);
         } else {
-            initialize_path(world, &sprite_sheet_handle);
+            initialize_level(world, self.level.take());
+            let keys = initialize_path(world, &sprite_sheet_handle);
             initialize_ball(world, &sprite_sheet_handle);
             videographer = initialize_videographer(world);
             initialize_camera(world, videographer);
             init_pedometer(world);
+            initialize_fog(world);
+            initialize_collectibles(world, &sprite_sheet_handle);
+            initialize_ghost(world, &sprite_sheet_handle);
+            init_segment_graph(world);
+            initialize_npcs(world, &sprite_sheet_handle);
+            initialize_hazards(world, &sprite_sheet_handle);
+            load_keys(world, keys, &sprite_sheet_handle);
+            load_inventory(world, Inventory::default());
         }
 
-        init_coordinates(world);
+        if !loaded {
+            world.insert(CurrentSave::default());
+        }
+        // a save from after the level was done has already had its level complete screen
+        self.completion_shown = world.read_resource::<Level>().complete;
+        init_collection(world);
+        world.insert(Race::default());
+        init_speedrun(world, !loaded);
+        init_hud(world);
+        world.insert(Pause::default());
+        world.insert(Controls::default());
+        world.insert(Route::default());
+        world.insert(UnsavedChanges::default());
+        let session = Session::start(&world.read_resource::<Pedometer>());
+        world.insert(session);
+        // a replay already knows where it's going
+        let autopilot = world.read_resource::<WanderballConfig>().autopilot
+            && !world.read_resource::<Playback>().active();
+        world.insert(Autopilot::new(autopilot));
+        start_recording(world, loaded);
         start_audio(world);
     }
 
+    fn on_pause(&mut self, state_data: StateData<'_, GameData<'_, '_>>) {
+        state_data.world.write_resource::<Pause>().paused = true;
+    }
+
+    fn on_resume(&mut self, state_data: StateData<'_, GameData<'_, '_>>) {
+        state_data.world.write_resource::<Pause>().paused = false;
+    }
+
+    fn update(&mut self, state_data: StateData<'_, GameData<'_, '_>>) -> GameTrans {
+        state_data.data.update(state_data.world);
+
+        if !self.completion_shown && state_data.world.read_resource::<Level>().complete {
+            self.completion_shown = true;
+            log::info!("[Trans::Push] level complete");
+            return Trans::Push(Box::new(LevelComplete::default()));
+        }
+
+        Trans::None
+    }
+
     fn handle_event(
         &mut self,
-        _: StateData<'_, GameData<'_, '_>>,
-        event: StateEvent,
-    ) -> SimpleTrans {
+        state_data: StateData<'_, GameData<'_, '_>>,
+        event: GameStateEvent,
+    ) -> GameTrans {
         match &event {
             StateEvent::Window(event) => {
                 if is_close_requested(&event) {
-                    log::info!("[Trans::Quit] quitting wanderball");
-                    Trans::Quit
+                    if state_data
+                        .world
+                        .read_resource::<WanderballConfig>()
+                        .summary_on_close
+                    {
+                        log::info!("[Trans::Push] session summary before quitting");
+                        Trans::Push(Box::new(Summary::default()))
+                    } else {
+                        log::info!("[Trans::Quit] quitting wanderball");
+                        Trans::Quit
+                    }
                 } else if is_key_down(&event, VirtualKeyCode::Escape) {
                     log::info!("[Trans::Switch] switching to menu");
                     Trans::Push(Box::new(Menu::default()))
@@ -72,12 +194,23 @@ impl SimpleState for Wanderball {
                     Trans::None
                 }
             }
+            StateEvent::Input(InputEvent::ActionPressed(ActionBinding::Pause)) => {
+                log::info!("[Trans::Push] pausing");
+                Trans::Push(Box::new(Menu::default()))
+            }
             _ => Trans::None,
         }
     }
 
     fn on_stop(&mut self, state_data: StateData<GameData>) {
         let StateData { world, .. } = state_data;
+
+        let recording = std::mem::take(&mut *world.write_resource::<Recording>());
+        if recording.active && !recording.replay.frames.is_empty() {
+            write_replay(&recording.replay);
+        }
+        world.insert(Playback::default());
+
         world.write_storage::<Ball>().clear();
         world.write_storage::<PathSegment>().clear();
         world.write_storage::<Videographer>().clear();
@@ -85,3 +218,30 @@ impl SimpleState for Wanderball {
         world.delete_all();
     }
 }
+
+/// Fresh games get recorded so they can be replayed later, loaded games, replays and autopilot
+/// runs don't
+fn start_recording(world: &mut World, loaded: bool) {
+    let record_replays = world.read_resource::<WanderballConfig>().record_replays;
+    let replaying = world.read_resource::<Playback>().active();
+    let autopilot = world.read_resource::<Autopilot>().active;
+
+    let recording = if record_replays && !loaded && !replaying && !autopilot {
+        let level = world.read_resource::<Level>();
+        Recording {
+            active: true,
+            replay: ReplayRecord {
+                version: REPLAY_VERSION,
+                level: level.number,
+                seed: level.seed,
+                path_length: level.path_length,
+                frames: vec![],
+                positions: vec![],
+            },
+        }
+    } else {
+        Recording::default()
+    };
+
+    world.insert(recording);
+}
@@ -0,0 +1,250 @@
+use amethyst::{
+    ecs::prelude::Entity,
+    input::{is_close_requested, Button, InputHandler},
+    prelude::*,
+    ui::{UiCreator, UiEvent, UiEventType, UiFinder, UiText},
+    winit::{ElementState, Event, KeyboardInput, VirtualKeyCode, WindowEvent},
+};
+
+use crate::components::player::{Player, MAX_PLAYERS};
+use crate::config::WanderballConfig;
+use crate::resources::bindings::{
+    rebind, rebindable, write_bindings, BindingSlot, GameBindings, RebindError,
+};
+use crate::resources::locale::Locale;
+use crate::states::navigation::MenuNavigation;
+use crate::states::{GameStateEvent, GameTrans};
+
+const LABEL_MESSAGE: &str = "key_bindings_message";
+const BUTTON_PLAYER: &str = "key_bindings_player";
+const LABEL_PLAYER: &str = "key_bindings_player_value";
+const BUTTON_BACK: &str = "key_bindings_back";
+/// One row per thing a player can rebind, "key_bindings_{row}" and "key_bindings_{row}_key"
+const ROWS: usize = 8;
+
+/// Pushed on top of the settings. Pick something to rebind then press the key for it, keys
+/// already in use swap over and the bindings are saved as soon as they change.
+#[derive(Default, Debug)]
+pub struct KeyBindings {
+    ui_root: Option<Entity>,
+    navigation: MenuNavigation,
+    label_message: Option<Entity>,
+    button_player: Option<Entity>,
+    label_player: Option<Entity>,
+    button_back: Option<Entity>,
+    buttons: Vec<Option<Entity>>,
+    labels: Vec<Option<Entity>>,
+    player: usize,
+    /// the row waiting on a key press
+    waiting: Option<usize>,
+    message: String,
+}
+
+impl KeyBindings {
+    fn slots(&self) -> Vec<BindingSlot> {
+        rebindable(Player::new(self.player))
+    }
+
+    fn bind(&mut self, world: &mut World, row: usize, key: VirtualKeyCode) {
+        let slot = match self.slots().get(row) {
+            Some(slot) => *slot,
+            None => return,
+        };
+
+        let rebound = {
+            let input = world.read_resource::<InputHandler<GameBindings>>();
+            rebind(&input.bindings, &slot, Button::Key(key))
+        };
+
+        self.message = match rebound {
+            Ok((bindings, conflict)) => {
+                write_bindings(&bindings);
+                world
+                    .write_resource::<InputHandler<GameBindings>>()
+                    .bindings = bindings;
+                match conflict {
+                    Some(conflict) => world.read_resource::<Locale>().format(
+                        "key_bindings.swapped",
+                        &[&format!("{:?}", key), &conflict.name()],
+                    ),
+                    None => String::new(),
+                }
+            }
+            Err(RebindError::Taken(other)) => world.read_resource::<Locale>().format(
+                "key_bindings.taken",
+                &[&format!("{:?}", key), &other.name(), &slot.name()],
+            ),
+            Err(RebindError::Failed(error)) => {
+                log::error!("couldn't rebind {}: {}", slot.name(), error);
+                world.read_resource::<Locale>().format(
+                    "key_bindings.failed",
+                    &[&format!("{:?}", key), &slot.name()],
+                )
+            }
+        };
+    }
+}
+
+impl State<GameData<'static, 'static>, GameStateEvent> for KeyBindings {
+    fn on_start(&mut self, state_data: StateData<'_, GameData<'_, '_>>) {
+        let StateData { world, .. } = state_data;
+
+        self.ui_root = Some(
+            world.exec(|mut creator: UiCreator<'_>| creator.create("ui/key_bindings.ron", ())),
+        );
+    }
+
+    fn update(&mut self, state_data: StateData<'_, GameData<'_, '_>>) -> GameTrans {
+        let StateData { world, data } = state_data;
+        data.update(world);
+        self.navigation.find(world, self.ui_root);
+
+        if self.button_back.is_none() || self.labels.iter().flatten().count() < ROWS {
+            world.exec(|ui_finder: UiFinder<'_>| {
+                self.label_message = ui_finder.find(LABEL_MESSAGE);
+                self.button_player = ui_finder.find(BUTTON_PLAYER);
+                self.label_player = ui_finder.find(LABEL_PLAYER);
+                self.button_back = ui_finder.find(BUTTON_BACK);
+                self.buttons = (0..ROWS)
+                    .map(|row| ui_finder.find(&format!("key_bindings_{}", row)))
+                    .collect();
+                self.labels = (0..ROWS)
+                    .map(|row| ui_finder.find(&format!("key_bindings_{}_key", row)))
+                    .collect();
+            });
+        }
+
+        let keys: Vec<String> = {
+            let input = world.read_resource::<InputHandler<GameBindings>>();
+            let locale = world.read_resource::<Locale>();
+            self.slots()
+                .iter()
+                .enumerate()
+                .map(|(row, slot)| {
+                    if self.waiting == Some(row) {
+                        locale.text("key_bindings.waiting")
+                    } else {
+                        match slot.button(&input.bindings) {
+                            Some(Button::Key(key)) => format!("{:?}", key),
+                            Some(button) => format!("{:?}", button),
+                            None => "-".to_string(),
+                        }
+                    }
+                })
+                .collect()
+        };
+
+        let mut ui_text = world.write_storage::<UiText>();
+        for (label, key) in self.labels.iter().zip(keys) {
+            if let Some(text) = label.and_then(|label| ui_text.get_mut(label)) {
+                text.text = key;
+            }
+        }
+        if let Some(text) = self.label_player.and_then(|label| ui_text.get_mut(label)) {
+            text.text = Player::new(self.player).name();
+        }
+        if let Some(text) = self.label_message.and_then(|label| ui_text.get_mut(label)) {
+            text.text = self.message.clone();
+        }
+
+        Trans::None
+    }
+
+    fn handle_event(
+        &mut self,
+        state_data: StateData<'_, GameData<'_, '_>>,
+        event: GameStateEvent,
+    ) -> GameTrans {
+        let StateData { world, .. } = state_data;
+
+        match event {
+            StateEvent::Window(event) => {
+                if is_close_requested(&event) {
+                    return Trans::Quit;
+                }
+                // while it's waiting on a key, every key is a binding, even the ones that move
+                // between the buttons
+                if self.waiting.is_none() && self.navigation.handle_event(world, &event) {
+                    return Trans::None;
+                }
+
+                let key = match event {
+                    Event::WindowEvent {
+                        event:
+                            WindowEvent::KeyboardInput {
+                                input:
+                                    KeyboardInput {
+                                        state: ElementState::Pressed,
+                                        virtual_keycode: Some(key),
+                                        ..
+                                    },
+                                ..
+                            },
+                        ..
+                    } => key,
+                    _ => return Trans::None,
+                };
+
+                // escape backs out of waiting for a key, then out of the screen
+                match (self.waiting.take(), key) {
+                    (Some(_), VirtualKeyCode::Escape) => Trans::None,
+                    (Some(row), key) => {
+                        self.bind(world, row, key);
+                        Trans::None
+                    }
+                    (None, VirtualKeyCode::Escape) => Trans::Pop,
+                    (None, _) => Trans::None,
+                }
+            }
+            StateEvent::Ui(UiEvent {
+                event_type: UiEventType::Click,
+                target,
+            }) => {
+                if Some(target) == self.button_back {
+                    log::info!("[Trans::Pop] closing key bindings");
+                    return Trans::Pop;
+                }
+
+                if Some(target) == self.button_player {
+                    let players = world
+                        .read_resource::<WanderballConfig>()
+                        .players
+                        .clamp(1, MAX_PLAYERS);
+                    self.player = (self.player + 1) % players;
+                    self.waiting = None;
+                    self.message = String::new();
+                }
+
+                if let Some(row) = self
+                    .buttons
+                    .iter()
+                    .position(|button| *button == Some(target))
+                {
+                    self.waiting = Some(row);
+                    self.message = String::new();
+                }
+
+                Trans::None
+            }
+            _ => Trans::None,
+        }
+    }
+
+    fn on_stop(&mut self, data: StateData<GameData>) {
+        if let Some(root_entity) = self.ui_root {
+            data.world
+                .delete_entity(root_entity)
+                .expect("failed to close key bindings");
+        }
+
+        self.ui_root = None;
+        self.navigation = MenuNavigation::default();
+        self.label_message = None;
+        self.button_player = None;
+        self.label_player = None;
+        self.button_back = None;
+        self.buttons = vec![];
+        self.labels = vec![];
+        self.waiting = None;
+    }
+}
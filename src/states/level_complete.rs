@@ -0,0 +1,155 @@
+use amethyst::{
+    ecs::prelude::Entity,
+    input::is_close_requested,
+    prelude::*,
+    shrev::EventChannel,
+    ui::{UiCreator, UiEvent, UiEventType, UiFinder, UiText},
+};
+
+use crate::components::collectible::Collection;
+use crate::components::level::Level;
+use crate::components::player::Race;
+use crate::components::wanderdata::Pedometer;
+use crate::config::WanderballConfig;
+use crate::resources::locale::Locale;
+use crate::resources::save::GameRecord;
+use crate::states::game::Wanderball;
+use crate::states::navigation::MenuNavigation;
+use crate::states::summary::{summarize, Summary};
+use crate::states::{GameStateEvent, GameTrans, GameTransEvent};
+
+const LABEL_STATS: &str = "level_complete_stats";
+const BUTTON_NEXT_LEVEL: &str = "next_level";
+const BUTTON_QUIT: &str = "level_complete_quit";
+
+/// Pushed on top of the game when the ball reaches the goal
+#[derive(Default, Debug)]
+pub struct LevelComplete {
+    ui_root: Option<Entity>,
+    navigation: MenuNavigation,
+    label_stats: Option<Entity>,
+    button_next_level: Option<Entity>,
+    button_quit: Option<Entity>,
+}
+
+impl State<GameData<'static, 'static>, GameStateEvent> for LevelComplete {
+    fn on_start(&mut self, state_data: StateData<'_, GameData<'_, '_>>) {
+        let StateData { world, .. } = state_data;
+
+        self.ui_root = Some(
+            world.exec(|mut creator: UiCreator<'_>| creator.create("ui/level_complete.ron", ())),
+        );
+    }
+
+    fn update(&mut self, state_data: StateData<'_, GameData<'_, '_>>) -> GameTrans {
+        let StateData { world, data } = state_data;
+        data.update(world);
+        self.navigation.find(world, self.ui_root);
+
+        if self.label_stats.is_none()
+            || self.button_next_level.is_none()
+            || self.button_quit.is_none()
+        {
+            world.exec(|ui_finder: UiFinder<'_>| {
+                self.label_stats = ui_finder.find(LABEL_STATS);
+                self.button_next_level = ui_finder.find(BUTTON_NEXT_LEVEL);
+                self.button_quit = ui_finder.find(BUTTON_QUIT);
+            });
+        }
+
+        if let Some(label_stats) = self.label_stats {
+            let locale = world.read_resource::<Locale>();
+            let headline = match world.try_fetch::<Race>().and_then(|race| race.winner) {
+                Some(winner) => locale.format("level_complete.race_won", &[&winner.name()]),
+                None => locale.format(
+                    "level_complete.headline",
+                    &[&world.read_resource::<Level>().number],
+                ),
+            };
+            let stats = format!(
+                "{}\n\n{}",
+                headline,
+                summarize(
+                    &world.read_resource::<Pedometer>(),
+                    &world.read_resource::<Collection>(),
+                    &locale,
+                )
+            );
+            if let Some(text) = world.write_storage::<UiText>().get_mut(label_stats) {
+                text.text = stats;
+            }
+        }
+
+        Trans::None
+    }
+
+    fn handle_event(
+        &mut self,
+        state_data: StateData<'_, GameData<'_, '_>>,
+        event: GameStateEvent,
+    ) -> GameTrans {
+        match event {
+            StateEvent::Window(event) => {
+                if self.navigation.handle_event(state_data.world, &event) {
+                    Trans::None
+                } else if is_close_requested(&event) {
+                    Trans::Quit
+                } else {
+                    Trans::None
+                }
+            }
+            StateEvent::Ui(UiEvent {
+                event_type: UiEventType::Click,
+                target,
+            }) => {
+                if Some(target) == self.button_next_level {
+                    let next_level = state_data
+                        .world
+                        .read_resource::<Level>()
+                        .next(&state_data.world.read_resource::<WanderballConfig>());
+
+                    if let Some(mut game_record) =
+                        state_data.world.try_fetch_mut::<Option<GameRecord>>()
+                    {
+                        // a loaded game would otherwise get loaded all over again
+                        (*game_record) = None;
+                    }
+
+                    let mut state_transition_event_channel = state_data
+                        .world
+                        .write_resource::<EventChannel<GameTransEvent>>();
+
+                    log::info!("set up state transitions for level {}", next_level.number);
+                    // 'Pop' back to the finished game so it can clean up in on_stop before the next level starts
+                    state_transition_event_channel.single_write(Box::new(|| Trans::Pop));
+                    state_transition_event_channel.single_write(Box::new(move || {
+                        Trans::Switch(Box::new(Wanderball::at_level(next_level.clone())))
+                    }));
+
+                    return Trans::None;
+                }
+                if Some(target) == self.button_quit {
+                    log::info!("[Trans::Switch] switching to summary");
+                    return Trans::Switch(Box::new(Summary::default()));
+                }
+
+                Trans::None
+            }
+            _ => Trans::None,
+        }
+    }
+
+    fn on_stop(&mut self, data: StateData<GameData>) {
+        if let Some(root_entity) = self.ui_root {
+            data.world
+                .delete_entity(root_entity)
+                .expect("failed to close level complete screen");
+        }
+
+        self.ui_root = None;
+        self.navigation = MenuNavigation::default();
+        self.label_stats = None;
+        self.button_next_level = None;
+        self.button_quit = None;
+    }
+}
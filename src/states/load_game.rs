@@ -0,0 +1,407 @@
+use amethyst::{
+    core::Parent,
+    ecs::{prelude::Entity, Join},
+    input::{is_close_requested, is_key_down},
+    prelude::*,
+    shrev::EventChannel,
+    ui::{Selected, UiCreator, UiEvent, UiEventType, UiFinder, UiText},
+    winit::VirtualKeyCode,
+};
+
+use crate::resources::locale::Locale;
+use crate::resources::saves::{
+    delete_save, list_saves, rename_save, CurrentSave, RenameError, SaveSlot,
+};
+use crate::states::confirm::{take_choice, Confirm};
+use crate::states::loading::Loading;
+use crate::states::navigation::MenuNavigation;
+use crate::states::settings::{hide_ui, show_ui};
+use crate::states::{GameStateEvent, GameTrans, GameTransEvent};
+
+const LABEL_MESSAGE: &str = "load_game_message";
+const LABEL_PAGE: &str = "load_game_page";
+const FIELD_NAME: &str = "load_game_name";
+const BUTTON_RENAME: &str = "load_game_rename";
+const BUTTON_DELETE: &str = "load_game_delete";
+const BUTTON_LOAD: &str = "load_game_load";
+const BUTTON_MORE: &str = "load_game_more";
+const BUTTON_BACK: &str = "load_game_back";
+/// Saves shown at once, "load_game_{row}" for each, the rest are a page further on
+const ROWS: usize = 5;
+
+const SELECTED_COLOR: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
+const UNSELECTED_COLOR: [f32; 4] = [0.7, 0.7, 0.7, 1.0];
+
+/// Lists every save to pick one to carry on from, they can be renamed or deleted from here too.
+/// Pushed on top of the start screen or the menu.
+#[derive(Default, Debug)]
+pub struct LoadGame {
+    ui_root: Option<Entity>,
+    label_message: Option<Entity>,
+    label_page: Option<Entity>,
+    field_name: Option<Entity>,
+    button_rename: Option<Entity>,
+    button_delete: Option<Entity>,
+    button_load: Option<Entity>,
+    button_more: Option<Entity>,
+    button_back: Option<Entity>,
+    buttons: Vec<Option<Entity>>,
+    /// the text on each row's button
+    labels: Vec<Option<Entity>>,
+    navigation: MenuNavigation,
+    saves: Vec<SaveSlot>,
+    selected: usize,
+    /// the name in the name field, it's only filled in again when the selection changes so
+    /// typing a new one isn't undone
+    shown_name: Option<String>,
+    message: String,
+    /// loading from the menu pops the menu as well on the way back to the game
+    over_menu: bool,
+}
+
+impl LoadGame {
+    pub fn over_menu() -> LoadGame {
+        LoadGame {
+            over_menu: true,
+            ..Default::default()
+        }
+    }
+
+    fn page(&self) -> usize {
+        self.selected / ROWS
+    }
+
+    fn refresh(&mut self) {
+        self.saves = list_saves();
+        self.selected = self.selected.min(self.saves.len().saturating_sub(1));
+        self.shown_name = None;
+    }
+
+    fn select(&mut self, selected: usize) {
+        if selected < self.saves.len() && selected != self.selected {
+            self.selected = selected;
+            self.message = String::new();
+        }
+    }
+
+    fn load(&self, world: &mut World) -> GameTrans {
+        let save = match self.saves.get(self.selected) {
+            Some(save) => save.clone(),
+            None => return Trans::None,
+        };
+
+        log::info!("[Trans::Switch] loading {}", save.name);
+        // out from under the load screen, and the menu if there is one, before switching so
+        // whatever's underneath can clean up after itself
+        let mut state_transition_event_channel =
+            world.write_resource::<EventChannel<GameTransEvent>>();
+        state_transition_event_channel.single_write(Box::new(|| Trans::Pop));
+        if self.over_menu {
+            state_transition_event_channel.single_write(Box::new(|| Trans::Pop));
+        }
+        state_transition_event_channel.single_write(Box::new(move || {
+            Trans::Switch(Box::new(Loading::new(save.clone())))
+        }));
+
+        Trans::None
+    }
+
+    fn ask_to_delete(&self, world: &World) -> GameTrans {
+        match self.saves.get(self.selected) {
+            Some(save) => {
+                log::info!("[Trans::Push] confirm deleting {}", save.name);
+                Trans::Push(Box::new(Confirm::new(
+                    BUTTON_DELETE,
+                    &world
+                        .read_resource::<Locale>()
+                        .format("load_game.confirm_delete", &[&save.name]),
+                )))
+            }
+            None => Trans::None,
+        }
+    }
+
+    fn delete(&mut self, world: &World) {
+        if let Some(save) = self.saves.get(self.selected) {
+            delete_save(save);
+            self.message = world
+                .read_resource::<Locale>()
+                .format("load_game.deleted", &[&save.name]);
+            self.refresh();
+        }
+    }
+
+    /// Puts the cursor in the name field, typing goes there until enter renames the save
+    fn start_typing(&mut self, world: &World) {
+        if let Some(field) = self.field_name {
+            let _ = world.write_storage::<Selected>().insert(field, Selected);
+            self.message = world.read_resource::<Locale>().text("load_game.type_name");
+        }
+    }
+
+    fn stop_typing(&self, world: &World) {
+        if let Some(field) = self.field_name {
+            world.write_storage::<Selected>().remove(field);
+        }
+    }
+
+    fn typed_name(&self, world: &World) -> Option<String> {
+        self.field_name.and_then(|field| {
+            world
+                .read_storage::<UiText>()
+                .get(field)
+                .map(|text| text.text.clone())
+        })
+    }
+
+    fn rename(&mut self, world: &mut World) {
+        let save = match self.saves.get(self.selected) {
+            Some(save) => save.clone(),
+            None => return,
+        };
+        let name = match self.typed_name(world) {
+            Some(name) => name,
+            None => return,
+        };
+
+        let locale = world.read_resource::<Locale>();
+        match rename_save(&save, &name) {
+            Ok(name) => {
+                // the game in progress keeps saving to it under its new name
+                if let Some(mut current_save) = world.try_fetch_mut::<CurrentSave>() {
+                    if current_save.name.as_ref() == Some(&save.name) {
+                        current_save.name = Some(name.clone());
+                    }
+                }
+                self.refresh();
+                if let Some(renamed) = self.saves.iter().position(|save| save.name == name) {
+                    self.selected = renamed;
+                }
+                self.message = locale.format("load_game.renamed", &[&name]);
+            }
+            Err(RenameError::NoName) => self.message = locale.text("load_game.no_name"),
+            Err(RenameError::Taken(name)) => {
+                self.message = locale.format("load_game.name_taken", &[&name])
+            }
+            Err(RenameError::Failed(error)) => {
+                log::error!("couldn't rename save {}: {}", save.name, error);
+                self.message = locale.format("load_game.rename_failed", &[&error])
+            }
+        }
+    }
+}
+
+impl State<GameData<'static, 'static>, GameStateEvent> for LoadGame {
+    fn on_start(&mut self, state_data: StateData<'_, GameData<'_, '_>>) {
+        let StateData { world, .. } = state_data;
+
+        self.refresh();
+        self.selected = 0;
+        self.ui_root =
+            Some(world.exec(|mut creator: UiCreator<'_>| creator.create("ui/load_game.ron", ())));
+    }
+
+    fn on_pause(&mut self, state_data: StateData<'_, GameData<'_, '_>>) {
+        hide_ui(state_data.world, self.ui_root);
+    }
+
+    fn on_resume(&mut self, state_data: StateData<'_, GameData<'_, '_>>) {
+        show_ui(state_data.world, self.ui_root);
+    }
+
+    fn update(&mut self, state_data: StateData<'_, GameData<'_, '_>>) -> GameTrans {
+        let StateData { world, data } = state_data;
+        data.update(world);
+
+        if let Some(choice) = take_choice(world) {
+            if choice.id == BUTTON_DELETE && choice.confirmed {
+                self.delete(world);
+            }
+        }
+
+        if self.button_back.is_none() || self.labels.iter().flatten().count() < ROWS {
+            world.exec(|ui_finder: UiFinder<'_>| {
+                self.label_message = ui_finder.find(LABEL_MESSAGE);
+                self.label_page = ui_finder.find(LABEL_PAGE);
+                self.field_name = ui_finder.find(FIELD_NAME);
+                self.button_rename = ui_finder.find(BUTTON_RENAME);
+                self.button_delete = ui_finder.find(BUTTON_DELETE);
+                self.button_load = ui_finder.find(BUTTON_LOAD);
+                self.button_more = ui_finder.find(BUTTON_MORE);
+                self.button_back = ui_finder.find(BUTTON_BACK);
+                self.buttons = (0..ROWS)
+                    .map(|row| ui_finder.find(&format!("load_game_{}", row)))
+                    .collect();
+            });
+            self.navigation.find(world, self.ui_root);
+            // a button's text is a child of it
+            let parents = world.read_storage::<Parent>();
+            let texts = world.read_storage::<UiText>();
+            self.labels = self
+                .buttons
+                .iter()
+                .map(|button| {
+                    let button = (*button)?;
+                    (&world.entities(), &parents, &texts)
+                        .join()
+                        .find(|(_, parent, _)| parent.entity == button)
+                        .map(|(label, _, _)| label)
+                })
+                .collect();
+        }
+
+        let page = self.page();
+        let pages = (self.saves.len() + ROWS - 1) / ROWS;
+        let focused = self.navigation.focused();
+        let locale = world.read_resource::<Locale>();
+        let mut ui_text = world.write_storage::<UiText>();
+
+        for (row, label) in self.labels.iter().enumerate() {
+            let index = page * ROWS + row;
+            if let Some(text) = label.and_then(|label| ui_text.get_mut(label)) {
+                match self.saves.get(index) {
+                    Some(save) => {
+                        text.text = format!("{}  {}", save.name, save.details(&locale));
+                        // the row the keyboard's on keeps its highlight
+                        if focused != self.buttons[row] {
+                            text.color = if index == self.selected {
+                                SELECTED_COLOR
+                            } else {
+                                UNSELECTED_COLOR
+                            };
+                        }
+                    }
+                    None => text.text = String::new(),
+                }
+            }
+        }
+
+        let name = self.saves.get(self.selected).map(|save| save.name.clone());
+        if name != self.shown_name {
+            if let Some(text) = self.field_name.and_then(|field| ui_text.get_mut(field)) {
+                text.text = name.clone().unwrap_or_default();
+                self.shown_name = name;
+            }
+        }
+
+        if let Some(text) = self.label_page.and_then(|label| ui_text.get_mut(label)) {
+            text.text = if pages > 1 {
+                locale.format("load_game.page", &[&(page + 1), &pages])
+            } else {
+                String::new()
+            };
+        }
+        if let Some(text) = self.label_message.and_then(|label| ui_text.get_mut(label)) {
+            text.text = if self.saves.is_empty() {
+                locale.text("load_game.empty")
+            } else {
+                self.message.clone()
+            };
+        }
+
+        Trans::None
+    }
+
+    fn handle_event(
+        &mut self,
+        state_data: StateData<'_, GameData<'_, '_>>,
+        event: GameStateEvent,
+    ) -> GameTrans {
+        let StateData { world, .. } = state_data;
+
+        match event {
+            StateEvent::Window(event) => {
+                if is_close_requested(&event) {
+                    return Trans::Quit;
+                }
+
+                // keys typed into the name field are for the name, escape leaves it alone
+                let typing = self
+                    .field_name
+                    .is_some_and(|field| world.read_storage::<Selected>().contains(field));
+                if typing {
+                    if is_key_down(&event, VirtualKeyCode::Escape) {
+                        self.stop_typing(world);
+                        self.message = String::new();
+                    }
+                    return Trans::None;
+                }
+
+                if is_key_down(&event, VirtualKeyCode::Escape) {
+                    log::info!("[Trans::Pop] closing load game");
+                    return Trans::Pop;
+                }
+
+                self.navigation.handle_event(world, &event);
+                Trans::None
+            }
+            StateEvent::Ui(UiEvent {
+                event_type: UiEventType::ValueCommit,
+                target,
+            }) if Some(target) == self.field_name => {
+                self.stop_typing(world);
+                self.rename(world);
+                Trans::None
+            }
+            StateEvent::Ui(UiEvent {
+                event_type: UiEventType::Click,
+                target,
+            }) => {
+                let target = Some(target);
+
+                if target == self.button_back {
+                    log::info!("[Trans::Pop] closing load game");
+                    return Trans::Pop;
+                }
+                if target == self.button_load {
+                    return self.load(world);
+                }
+
+                if target == self.button_delete {
+                    return self.ask_to_delete(world);
+                } else if target == self.button_rename {
+                    // nothing new typed yet, so it's time to type one
+                    if self.typed_name(world) == self.shown_name {
+                        self.start_typing(world);
+                    } else {
+                        self.rename(world);
+                    }
+                } else if target == self.field_name {
+                    self.start_typing(world);
+                } else if target == self.button_more {
+                    // on to the first save of the next page, back round to the start after the last
+                    let next = (self.page() + 1) * ROWS;
+                    self.selected = if next < self.saves.len() { next } else { 0 };
+                    self.message = String::new();
+                } else if let Some(row) = self.buttons.iter().position(|button| *button == target) {
+                    self.select(self.page() * ROWS + row);
+                }
+
+                Trans::None
+            }
+            _ => Trans::None,
+        }
+    }
+
+    fn on_stop(&mut self, data: StateData<GameData>) {
+        if let Some(root_entity) = self.ui_root {
+            data.world
+                .delete_entity(root_entity)
+                .expect("failed to close load game");
+        }
+
+        self.ui_root = None;
+        self.label_message = None;
+        self.label_page = None;
+        self.field_name = None;
+        self.button_rename = None;
+        self.button_delete = None;
+        self.button_load = None;
+        self.button_more = None;
+        self.button_back = None;
+        self.buttons = vec![];
+        self.labels = vec![];
+        self.navigation = MenuNavigation::default();
+        self.shown_name = None;
+    }
+}
@@ -1,32 +1,50 @@
-use amethyst::{ecs::Entity, prelude::*};
+use amethyst::prelude::*;
 
-use crate::resources::save::GameRecord;
+use crate::resources::saves::{read_save, CurrentSave, SaveSlot};
 use crate::states::game::Wanderball;
-use std::{fs::File, io::Read, path::Path};
+use crate::states::start::StartScreen;
+use crate::states::{GameStateEvent, GameTrans};
 
-#[derive(Default, Debug)]
+/// Reads a save picked on the load screen and starts the game from it
+#[derive(Debug)]
 pub struct Loading {
-    ui_root: Option<Entity>,
+    save: SaveSlot,
+    loaded: bool,
 }
 
-impl SimpleState for Loading {
+impl Loading {
+    pub fn new(save: SaveSlot) -> Loading {
+        Loading {
+            save,
+            loaded: false,
+        }
+    }
+}
+
+impl State<GameData<'static, 'static>, GameStateEvent> for Loading {
     fn on_start(&mut self, state_data: StateData<'_, GameData<'_, '_>>) {
         let StateData { world, .. } = state_data;
 
-        let save_dir = Path::new(".save");
-
-        if save_dir.exists() {
-            let game_record: GameRecord;
-            let save_file_path = save_dir.join("wanderball-save.json");
-            let mut f = File::open(save_file_path).unwrap();
-            let mut contents = String::new();
-            let _ = f.read_to_string(&mut contents);
-            game_record = serde_json::from_str(&contents).unwrap();
-            world.insert(Some(game_record));
+        match read_save(&self.save.path) {
+            Some(game_record) => {
+                world.insert(Some(game_record));
+                world.insert(CurrentSave {
+                    name: Some(self.save.name.clone()),
+                });
+                self.loaded = true;
+            }
+            None => log::error!("couldn't read the save {}", self.save.path.display()),
         }
     }
 
-    fn update(&mut self, _state_data: &mut StateData<'_, GameData<'_, '_>>) -> SimpleTrans {
-        Trans::Switch(Box::new(Wanderball::default()))
+    fn update(&mut self, state_data: StateData<'_, GameData<'_, '_>>) -> GameTrans {
+        state_data.data.update(state_data.world);
+
+        if self.loaded {
+            Trans::Switch(Box::new(Wanderball::default()))
+        } else {
+            log::info!("[Trans::Switch] back to the start screen");
+            Trans::Switch(Box::new(StartScreen::default()))
+        }
     }
 }
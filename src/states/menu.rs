@@ -1,35 +1,95 @@
 use amethyst::{
     ecs::prelude::Entity,
     input::{is_close_requested, is_key_down},
-    prelude::{GameData, SimpleState, SimpleTrans, StateData, StateEvent, Trans, WorldExt},
+    prelude::{GameData, State, StateData, StateEvent, Trans, World, WorldExt},
     shrev::EventChannel,
     ui::{UiCreator, UiEvent, UiEventType, UiFinder},
     winit::VirtualKeyCode,
-    TransEvent,
 };
 
+use crate::config::WanderballConfig;
+use crate::resources::locale::Locale;
 use crate::resources::save::GameRecord;
+use crate::resources::saves::UnsavedChanges;
+use crate::states::confirm::{take_choice, Confirm};
 use crate::states::game::Wanderball;
-use crate::states::loading::Loading;
+use crate::states::load_game::LoadGame;
+use crate::states::navigation::MenuNavigation;
 use crate::states::saving::Saving;
+use crate::states::settings::{hide_ui, show_ui, Settings};
+use crate::states::summary::Summary;
+use crate::states::{GameStateEvent, GameTrans, GameTransEvent};
 
 const BUTTON_RESUME: &str = "resume";
 const BUTTON_RESTART: &str = "restart";
 const BUTTON_SAVE: &str = "save";
 const BUTTON_LOAD: &str = "load";
+const BUTTON_SETTINGS: &str = "settings";
 const BUTTON_QUIT: &str = "quit";
 
 #[derive(Default, Debug)]
 pub struct Menu {
     ui_root: Option<Entity>,
+    navigation: MenuNavigation,
     button_resume: Option<Entity>,
     button_restart: Option<Entity>,
     button_save: Option<Entity>,
     button_load: Option<Entity>,
+    button_settings: Option<Entity>,
     button_quit: Option<Entity>,
 }
 
-impl SimpleState for Menu {
+impl Menu {
+    /// Restarting and loading both leave the game behind, so they check first if there's
+    /// anything that hasn't been saved. Quitting goes by the session summary, which asks itself.
+    fn leave(&self, world: &mut World, action: &'static str) -> GameTrans {
+        if !world.read_resource::<UnsavedChanges>().dirty {
+            return self.go(world, action);
+        }
+
+        let question = world.read_resource::<Locale>().text(match action {
+            BUTTON_RESTART => "menu.confirm_restart",
+            _ => "menu.confirm_load",
+        });
+        log::info!("[Trans::Push] confirm {}", action);
+        Trans::Push(Box::new(Confirm::new(action, &question)))
+    }
+
+    fn go(&self, world: &mut World, action: &str) -> GameTrans {
+        match action {
+            BUTTON_RESTART => {
+                let mut state_transition_event_channel =
+                    world.write_resource::<EventChannel<GameTransEvent>>();
+
+                if let Some(mut game_record) = world.try_fetch_mut::<Option<GameRecord>>() {
+                    // if we loaded a game this session, restarting will bump into
+                    // some already initialized state so we need to clear it out here
+                    log::info!("found loaded game state, setting it to None");
+                    (*game_record) = None;
+                }
+
+                log::info!("set up state transitions for a game restart");
+                // first 'Pop' the menu and get us to the game state below it. then when we switch, the old game can clean up it's resources in the on_stop handler before we start a new one.
+                state_transition_event_channel.single_write(Box::new(|| Trans::Pop));
+                state_transition_event_channel
+                    .single_write(Box::new(|| Trans::Switch(Box::new(Wanderball::default()))));
+
+                log::info!("[Trans::None] restart game");
+                Trans::None
+            }
+            BUTTON_LOAD => {
+                log::info!("[Trans::Push] load game");
+                Trans::Push(Box::new(LoadGame::over_menu()))
+            }
+            _ => {
+                log::info!("[Trans::Switch] switching to summary");
+                Trans::Switch(Box::new(Summary::default()))
+            }
+        }
+    }
+}
+
+impl State<GameData<'static, 'static>, GameStateEvent> for Menu {
     fn on_start(&mut self, state_data: StateData<'_, GameData<'_, '_>>) {
         let StateData { world, .. } = state_data;
 
@@ -37,14 +97,25 @@ impl SimpleState for Menu {
             Some(world.exec(|mut creator: UiCreator<'_>| creator.create("ui/menu.ron", ())));
     }
 
-    fn update(&mut self, state_data: &mut StateData<'_, GameData<'_, '_>>) -> SimpleTrans {
-        let StateData { world, .. } = state_data;
+    fn on_pause(&mut self, state_data: StateData<'_, GameData<'_, '_>>) {
+        hide_ui(state_data.world, self.ui_root);
+    }
+
+    fn on_resume(&mut self, state_data: StateData<'_, GameData<'_, '_>>) {
+        show_ui(state_data.world, self.ui_root);
+    }
+
+    fn update(&mut self, state_data: StateData<'_, GameData<'_, '_>>) -> GameTrans {
+        let StateData { world, data } = state_data;
+        data.update(world);
+        self.navigation.find(world, self.ui_root);
 
         // "find" buttons once
         if self.button_resume.is_none()
             || self.button_restart.is_none()
             || self.button_save.is_none()
             || self.button_load.is_none()
+            || self.button_settings.is_none()
             || self.button_quit.is_none()
         {
             world.exec(|ui_finder: UiFinder<'_>| {
@@ -52,22 +123,37 @@ impl SimpleState for Menu {
                 self.button_restart = ui_finder.find(BUTTON_RESTART);
                 self.button_save = ui_finder.find(BUTTON_SAVE);
                 self.button_load = ui_finder.find(BUTTON_LOAD);
+                self.button_settings = ui_finder.find(BUTTON_SETTINGS);
                 self.button_quit = ui_finder.find(BUTTON_QUIT);
             });
         }
 
-        Trans::None
+        // back from being asked whether to leave the game
+        match take_choice(world) {
+            Some(choice) if choice.confirmed => self.go(world, choice.id),
+            _ => Trans::None,
+        }
     }
 
     fn handle_event(
         &mut self,
         state_data: StateData<'_, GameData<'_, '_>>,
-        event: StateEvent,
-    ) -> SimpleTrans {
+        event: GameStateEvent,
+    ) -> GameTrans {
         match event {
             StateEvent::Window(event) => {
-                if is_close_requested(&event) {
-                    Trans::Quit
+                if self.navigation.handle_event(state_data.world, &event) {
+                    Trans::None
+                } else if is_close_requested(&event) {
+                    if state_data
+                        .world
+                        .read_resource::<WanderballConfig>()
+                        .summary_on_close
+                    {
+                        self.go(state_data.world, BUTTON_QUIT)
+                    } else {
+                        Trans::Quit
+                    }
                 } else if is_key_down(&event, VirtualKeyCode::Escape) {
                     Trans::Pop
                 } else {
@@ -84,27 +170,7 @@ impl SimpleState for Menu {
                 }
 
                 if Some(target) == self.button_restart {
-                    let mut state_transition_event_channel = state_data
-                        .world
-                        .write_resource::<EventChannel<TransEvent<GameData, StateEvent>>>();
-
-                    if let Some(mut game_record) =
-                        state_data.world.try_fetch_mut::<Option<GameRecord>>()
-                    {
-                        // if we loaded a game this session, restarting will bump into
-                        // some already initialized state so we need to clear it out here
-                        log::info!("found loaded game state, setting it to None");
-                        (*game_record) = None;
-                    }
-
-                    log::info!("set up state transitions for a game restart");
-                    // first 'Pop' the menu and get us to the game state below it. then when we switch, the old game can clean up it's resources in the on_stop handler before we start a new one.
-                    state_transition_event_channel.single_write(Box::new(|| Trans::Pop));
-                    state_transition_event_channel
-                        .single_write(Box::new(|| Trans::Switch(Box::new(Wanderball::default()))));
-
-                    log::info!("[Trans::None] restart game");
-                    return Trans::None;
+                    return self.leave(state_data.world, BUTTON_RESTART);
                 }
 
                 if Some(target) == self.button_save {
@@ -112,21 +178,14 @@ impl SimpleState for Menu {
                     return Trans::Push(Box::new(Saving::default()));
                 }
                 if Some(target) == self.button_load {
-                    let mut state_transition_event_channel = state_data
-                        .world
-                        .write_resource::<EventChannel<TransEvent<GameData, StateEvent>>>();
-
-                    log::info!("[Trans::None] load game");
-                    // this allows us to first 'Pop' the menu and get us to the game state below it. then when we switch, the old game can clean up it's resources in the on_stop handler before we load another one.
-                    state_transition_event_channel.single_write(Box::new(|| Trans::Pop));
-                    state_transition_event_channel
-                        .single_write(Box::new(|| Trans::Switch(Box::new(Loading::default()))));
-
-                    return Trans::None;
+                    return self.leave(state_data.world, BUTTON_LOAD);
+                }
+                if Some(target) == self.button_settings {
+                    log::info!("[Trans::Push] settings");
+                    return Trans::Push(Box::new(Settings::default()));
                 }
                 if Some(target) == self.button_quit {
-                    log::info!("[Trans::Quit] quit game");
-                    return Trans::Quit;
+                    return self.go(state_data.world, BUTTON_QUIT);
                 }
                 Trans::None
             }
@@ -142,10 +201,12 @@ impl SimpleState for Menu {
         }
 
         self.ui_root = None;
+        self.navigation = MenuNavigation::default();
         self.button_resume = None;
         self.button_restart = None;
         self.button_save = None;
         self.button_load = None;
+        self.button_settings = None;
         self.button_quit = None;
     }
 }
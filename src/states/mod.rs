@@ -1,5 +1,31 @@
+pub mod confirm;
 pub mod game;
+pub mod key_bindings;
+pub mod level_complete;
+pub mod load_game;
 pub mod loading;
 pub mod menu;
+pub mod navigation;
+pub mod replay;
 pub mod saving;
+pub mod settings;
 pub mod start;
+pub mod summary;
+
+use amethyst::{
+    prelude::{GameData, StateEvent, Trans},
+    TransEvent,
+};
+
+use crate::resources::bindings::GameBindings;
+
+/// Window, ui and input events, with input actions named by the game's own bindings
+pub type GameStateEvent = StateEvent<GameBindings>;
+
+/// Every state is a `State<GameData<'static, 'static>, GameStateEvent>` rather than a
+/// `SimpleState`, which only knows about string bindings. That also means each state's `update`
+/// has to run the dispatcher itself.
+pub type GameTrans = Trans<GameData<'static, 'static>, GameStateEvent>;
+
+/// For queueing up transitions from outside of `handle_event`
+pub type GameTransEvent = TransEvent<GameData<'static, 'static>, GameStateEvent>;
@@ -0,0 +1,147 @@
+use amethyst::{
+    core::Parent,
+    ecs::{prelude::Entity, Join},
+    prelude::*,
+    shrev::EventChannel,
+    ui::{Interactable, UiEvent, UiEventType, UiText, UiTransform},
+    winit::{ElementState, Event, KeyboardInput, VirtualKeyCode, WindowEvent},
+};
+
+use std::cmp::Ordering;
+
+/// The same blue the buttons turn when the mouse is over them
+const FOCUSED_COLOR: [f32; 4] = [0.2, 0.2, 1.0, 1.0];
+
+/// Lets a screen's buttons be used without a mouse. The arrow keys and tab move a highlight from
+/// button to button in their `tab_order`, top to bottom where that's the same, and enter or space
+/// clicks the highlighted one.
+#[derive(Debug, Default)]
+pub struct MenuNavigation {
+    /// every button under the screen's root with the text on it, in the order they're visited
+    buttons: Vec<(Entity, Option<Entity>)>,
+    focused: Option<usize>,
+    /// what the highlighted button's text looked like before it was highlighted
+    unfocused_color: Option<[f32; 4]>,
+}
+
+impl MenuNavigation {
+    /// Picks up the buttons under the screen's root, once they've been created
+    pub fn find(&mut self, world: &World, ui_root: Option<Entity>) {
+        let ui_root = match ui_root {
+            Some(ui_root) if self.buttons.is_empty() => ui_root,
+            _ => return,
+        };
+
+        let entities = world.entities();
+        let parents = world.read_storage::<Parent>();
+        let transforms = world.read_storage::<UiTransform>();
+        let interactables = world.read_storage::<Interactable>();
+        let texts = world.read_storage::<UiText>();
+
+        let under_root = |entity: Entity| {
+            let mut entity = entity;
+            while let Some(parent) = parents.get(entity) {
+                if parent.entity == ui_root {
+                    return true;
+                }
+                entity = parent.entity;
+            }
+            false
+        };
+
+        let mut buttons: Vec<(Entity, &UiTransform)> = (&entities, &transforms, &interactables)
+            .join()
+            .filter(|(entity, _, _)| under_root(*entity))
+            .map(|(entity, transform, _)| (entity, transform))
+            .collect();
+        buttons.sort_by(|(_, a), (_, b)| {
+            a.tab_order
+                .cmp(&b.tab_order)
+                .then(b.local_y.partial_cmp(&a.local_y).unwrap_or(Ordering::Equal))
+                .then(a.local_x.partial_cmp(&b.local_x).unwrap_or(Ordering::Equal))
+        });
+
+        // a button's text is a child of it
+        self.buttons = buttons
+            .into_iter()
+            .map(|(button, _)| {
+                let text = (&entities, &parents, &texts)
+                    .join()
+                    .find(|(_, parent, _)| parent.entity == button)
+                    .map(|(text, _, _)| text);
+                (button, text)
+            })
+            .collect();
+    }
+
+    /// The highlighted button, for screens that color their buttons themselves
+    pub fn focused(&self) -> Option<Entity> {
+        self.focused.map(|focused| self.buttons[focused].0)
+    }
+
+    /// Moves the highlight or clicks the highlighted button, true if the key was used up doing so
+    pub fn handle_event(&mut self, world: &mut World, event: &Event) -> bool {
+        let (key, shift) = match event {
+            Event::WindowEvent {
+                event:
+                    WindowEvent::KeyboardInput {
+                        input:
+                            KeyboardInput {
+                                state: ElementState::Pressed,
+                                virtual_keycode: Some(key),
+                                modifiers,
+                                ..
+                            },
+                        ..
+                    },
+                ..
+            } => (*key, modifiers.shift),
+            _ => return false,
+        };
+
+        match key {
+            VirtualKeyCode::Up | VirtualKeyCode::Left => self.step(world, false),
+            VirtualKeyCode::Down | VirtualKeyCode::Right => self.step(world, true),
+            VirtualKeyCode::Tab => self.step(world, !shift),
+            VirtualKeyCode::Return | VirtualKeyCode::Space => match self.focused {
+                // goes through the same way as a click so the screen needn't know the difference
+                Some(focused) => world
+                    .write_resource::<EventChannel<UiEvent>>()
+                    .single_write(UiEvent::new(UiEventType::Click, self.buttons[focused].0)),
+                None => return false,
+            },
+            _ => return false,
+        }
+
+        true
+    }
+
+    fn step(&mut self, world: &mut World, forwards: bool) {
+        if self.buttons.is_empty() {
+            return;
+        }
+
+        let last = self.buttons.len() - 1;
+        let next = match (self.focused, forwards) {
+            (None, true) => 0,
+            (None, false) => last,
+            (Some(focused), true) => (focused + 1) % self.buttons.len(),
+            (Some(focused), false) => focused.checked_sub(1).unwrap_or(last),
+        };
+
+        let mut ui_text = world.write_storage::<UiText>();
+        if let (Some(text), Some(color)) = (
+            self.focused
+                .and_then(|focused| self.buttons[focused].1)
+                .and_then(|text| ui_text.get_mut(text)),
+            self.unfocused_color.take(),
+        ) {
+            text.color = color;
+        }
+        if let Some(text) = self.buttons[next].1.and_then(|text| ui_text.get_mut(text)) {
+            self.unfocused_color = Some(text.color);
+            text.color = FOCUSED_COLOR;
+        }
+        self.focused = Some(next);
+    }
+}
@@ -0,0 +1,55 @@
+use amethyst::prelude::*;
+
+use crate::components::level::Level;
+use crate::resources::{controls::Playback, replay::latest_replay, save::GameRecord};
+use crate::states::game::Wanderball;
+use crate::states::start::StartScreen;
+use crate::states::{GameStateEvent, GameTrans};
+
+/// Sets up the most recent replay to be played back and hands off to the game
+#[derive(Default, Debug)]
+pub struct Replay {
+    level: Option<Level>,
+}
+
+impl State<GameData<'static, 'static>, GameStateEvent> for Replay {
+    fn on_start(&mut self, state_data: StateData<'_, GameData<'_, '_>>) {
+        let StateData { world, .. } = state_data;
+
+        if let Some(replay) = latest_replay() {
+            log::info!(
+                "replaying {} frames of level {}",
+                replay.frames.len(),
+                replay.level
+            );
+
+            // a replay always starts from a freshly generated path
+            let game_record: Option<GameRecord> = None;
+            world.insert(game_record);
+
+            self.level = Some(Level {
+                number: replay.level,
+                seed: replay.seed,
+                path_length: replay.path_length,
+                goal: None,
+                complete: false,
+            });
+            world.insert(Playback {
+                frames: replay.frames,
+                frame: 0,
+            });
+        }
+    }
+
+    fn update(&mut self, state_data: StateData<'_, GameData<'_, '_>>) -> GameTrans {
+        state_data.data.update(state_data.world);
+
+        match self.level.take() {
+            Some(level) => Trans::Switch(Box::new(Wanderball::at_level(level))),
+            None => {
+                log::error!("[Trans::Switch] no replays to watch");
+                Trans::Switch(Box::new(StartScreen::default()))
+            }
+        }
+    }
+}
@@ -7,26 +7,33 @@ use amethyst::{
 
 use crate::components::{
     ball::Ball,
+    collectible::Collectible,
+    fog::FogOfWar,
+    gate::{Gate, Inventory, Key},
+    hazard::{Checkpoint, Hazard, LastCheckpoint},
+    level::Level,
+    npc::Npc,
     path::PathSegment,
+    player::Player,
+    portal::Portals,
     shapes::{circle::Circle, rectangle::Rectangle},
+    surface::Surface,
     videographer::Videographer,
-    wanderdata::Pedometer,
+    wanderdata::{pedometer_record, Pedometer},
 };
 use crate::resources::save::{
-    BallRecord, CameraRecord, GameRecord, PathSegmentRecord, PedometerRecord, VideographerRecord,
-};
-use std::{
-    fs::{create_dir, File},
-    io::Write,
-    path::Path,
+    BallRecord, CameraRecord, CollectibleRecord, FogOfWarRecord, GameRecord, HazardRecord,
+    KeyRecord, LevelRecord, NpcRecord, PathSegmentRecord, PedometerRecord, VideographerRecord,
 };
+use crate::resources::saves::{next_save_name, write_save, CurrentSave, UnsavedChanges};
+use crate::states::{GameStateEvent, GameTrans};
 
 #[derive(Default, Debug)]
 pub struct Saving {
     ui_root: Option<Entity>,
 }
 
-impl SimpleState for Saving {
+impl State<GameData<'static, 'static>, GameStateEvent> for Saving {
     fn on_start(&mut self, state_data: StateData<'_, GameData<'_, '_>>) {
         log::info!("start saving state");
         let StateData { world, .. } = state_data;
@@ -39,28 +46,21 @@ impl SimpleState for Saving {
             // maybe save logic needs to start with just a record of high scores...
             // but then prob open up once the world does
 
-            // serialize
-            if let Ok(record) = serde_json::to_string(&game_record) {
-                log::info!("serialize save");
-                let save_dir = Path::new(".save");
-                let save_file_path = save_dir.join("wanderball-save.json");
-
-                if save_dir.exists() {
-                    if let Ok(mut f) = File::create(save_file_path) {
-                        let _ = f.write_all(record.as_bytes());
-                        log::info!("serialized save");
-                    }
-                } else if create_dir(save_dir).is_ok() {
-                    if let Ok(mut f) = File::create(save_file_path) {
-                        let _ = f.write_all(record.as_bytes());
-                        log::info!("serialized save");
-                    }
-                }
+            // a game that's been saved or loaded before keeps going in the same slot
+            let name = world
+                .write_resource::<CurrentSave>()
+                .name
+                .get_or_insert_with(next_save_name)
+                .clone();
+            if write_save(&name, &game_record) {
+                world.write_resource::<UnsavedChanges>().dirty = false;
             }
         }
     }
 
-    fn update(&mut self, _state_data: &mut StateData<'_, GameData<'_, '_>>) -> SimpleTrans {
+    fn update(&mut self, state_data: StateData<'_, GameData<'_, '_>>) -> GameTrans {
+        state_data.data.update(state_data.world);
+
         log::info!("[Trans::Pop] leave saving state");
         Trans::Pop
     }
@@ -75,6 +75,16 @@ fn build_save(world: &mut World) -> Option<GameRecord> {
     let tint_storage = world.read_storage::<Tint>();
     let videographer_storage = world.read_storage::<Videographer>();
     let camera_storage = world.read_storage::<Camera>();
+    let collectible_storage = world.read_storage::<Collectible>();
+    let player_storage = world.read_storage::<Player>();
+    let pedometer_storage = world.read_storage::<Pedometer>();
+    let npc_storage = world.read_storage::<Npc>();
+    let surface_storage = world.read_storage::<Surface>();
+    let checkpoint_storage = world.read_storage::<Checkpoint>();
+    let last_checkpoint_storage = world.read_storage::<LastCheckpoint>();
+    let hazard_storage = world.read_storage::<Hazard>();
+    let gate_storage = world.read_storage::<Gate>();
+    let key_storage = world.read_storage::<Key>();
     let transform_storage = world.read_storage::<Transform>();
     log::info!("collected all storages");
 
@@ -82,20 +92,40 @@ fn build_save(world: &mut World) -> Option<GameRecord> {
     let mut path_segments: Vec<PathSegmentRecord> = vec![];
     let mut videographer = VideographerRecord::default();
     let mut pedometer = PedometerRecord::default();
+    let mut fog = FogOfWarRecord::default();
+    let mut collectibles: Vec<CollectibleRecord> = vec![];
+    let mut npcs: Vec<NpcRecord> = vec![];
+    let mut hazards: Vec<HazardRecord> = vec![];
+    let mut keys: Vec<KeyRecord> = vec![];
     let mut maybe_camera: Option<CameraRecord> = None;
 
-    for (_ball, circle, transform) in (&ball_storage, &circle_storage, &transform_storage).join() {
+    for (_ball, circle, transform, player, ball_pedometer, last_checkpoint) in (
+        &ball_storage,
+        &circle_storage,
+        &transform_storage,
+        &player_storage,
+        &pedometer_storage,
+        last_checkpoint_storage.maybe(),
+    )
+        .join()
+    {
         balls.push(BallRecord {
             transform: transform.clone(),
             circle: circle.clone(),
+            player: Some(*player),
+            pedometer: Some(pedometer_record(ball_pedometer)),
+            checkpoint: last_checkpoint.copied(),
         })
     }
 
-    for (_segment, rectangle, tint, transform) in (
+    for (_segment, rectangle, tint, transform, surface, checkpoint, gate) in (
         &path_segment_storage,
         &rectangle_storage,
         &tint_storage,
         &transform_storage,
+        surface_storage.maybe(),
+        checkpoint_storage.maybe(),
+        gate_storage.maybe(),
     )
         .join()
     {
@@ -103,6 +133,41 @@ fn build_save(world: &mut World) -> Option<GameRecord> {
             transform: transform.clone(),
             rectangle: rectangle.clone(),
             tint: *tint,
+            surface: surface.copied().unwrap_or_default(),
+            checkpoint: checkpoint.is_some(),
+            gate: gate.map(|gate| gate.color),
+        })
+    }
+
+    for (key, transform) in (&key_storage, &transform_storage).join() {
+        keys.push(KeyRecord {
+            transform: transform.clone(),
+            key: key.clone(),
+        })
+    }
+
+    for (hazard, rectangle, transform) in
+        (&hazard_storage, &rectangle_storage, &transform_storage).join()
+    {
+        hazards.push(HazardRecord {
+            transform: transform.clone(),
+            rectangle: rectangle.clone(),
+            hazard: hazard.clone(),
+        })
+    }
+
+    for (npc, circle, transform) in (&npc_storage, &circle_storage, &transform_storage).join() {
+        npcs.push(NpcRecord {
+            transform: transform.clone(),
+            circle: circle.clone(),
+            npc: npc.clone(),
+        })
+    }
+
+    for (collectible, transform) in (&collectible_storage, &transform_storage).join() {
+        collectibles.push(CollectibleRecord {
+            transform: transform.clone(),
+            collectible: collectible.clone(),
         })
     }
 
@@ -114,12 +179,32 @@ fn build_save(world: &mut World) -> Option<GameRecord> {
     }
 
     if let Some(pedometer_resource) = world.try_fetch::<Pedometer>() {
-        pedometer = PedometerRecord {
-            steps: pedometer_resource.steps,
-            visited: pedometer_resource.visited.clone(),
+        pedometer = pedometer_record(&pedometer_resource);
+    }
+
+    if let Some(fog_resource) = world.try_fetch::<FogOfWar>() {
+        fog = FogOfWarRecord {
+            revealed: fog_resource.revealed.clone(),
         };
     }
 
+    let portals = world
+        .try_fetch::<Portals>()
+        .map(|portals| portals.pairs.clone())
+        .unwrap_or_default();
+
+    let inventory = world
+        .try_fetch::<Inventory>()
+        .map(|inventory| inventory.clone())
+        .unwrap_or_default();
+
+    let level = world.try_fetch::<Level>().map(|level| LevelRecord {
+        number: level.number,
+        seed: level.seed,
+        path_length: level.path_length,
+        goal: level.goal.clone(),
+    });
+
     for (camera_instance, transform) in (&camera_storage, &transform_storage).join() {
         maybe_camera = Some(CameraRecord {
             transform: transform.clone(),
@@ -135,6 +220,14 @@ fn build_save(world: &mut World) -> Option<GameRecord> {
             videographer,
             camera,
             pedometer,
+            fog,
+            collectibles,
+            level,
+            npcs,
+            hazards,
+            portals,
+            keys,
+            inventory,
         })
     } else {
         log::error!("couldn't find a camera!");
@@ -0,0 +1,300 @@
+use amethyst::{
+    core::HiddenPropagate,
+    ecs::prelude::Entity,
+    input::{is_close_requested, is_key_down},
+    prelude::*,
+    ui::{UiCreator, UiEvent, UiEventType, UiFinder, UiText},
+    window::Window,
+    winit::{dpi::LogicalSize, VirtualKeyCode},
+};
+
+use crate::audio::set_music_volume;
+use crate::components::videographer::CameraMode;
+use crate::config::WanderballConfig;
+use crate::resources::locale::Locale;
+use crate::resources::preferences::{write_preferences, Preferences};
+use crate::states::key_bindings::KeyBindings;
+use crate::states::navigation::MenuNavigation;
+use crate::states::{GameStateEvent, GameTrans};
+
+const BUTTON_VOLUME: &str = "settings_volume";
+const BUTTON_CAMERA: &str = "settings_camera";
+const BUTTON_ZOOM: &str = "settings_zoom";
+const BUTTON_HUD: &str = "settings_hud";
+const BUTTON_WINDOW: &str = "settings_window";
+const BUTTON_LANGUAGE: &str = "settings_language";
+const BUTTON_CONTROLS: &str = "settings_controls";
+const BUTTON_BACK: &str = "settings_back";
+const LABEL_VOLUME: &str = "settings_volume_value";
+const LABEL_CAMERA: &str = "settings_camera_value";
+const LABEL_ZOOM: &str = "settings_zoom_value";
+const LABEL_HUD: &str = "settings_hud_value";
+const LABEL_WINDOW: &str = "settings_window_value";
+const LABEL_LANGUAGE: &str = "settings_language_value";
+
+/// Each click turns the music up this much, going past full volume starts again from silent
+const VOLUME_STEP: f32 = 0.1;
+/// Bigger zoom factors take smaller steps, so these go from slowest to fastest
+const ZOOM_SPEEDS: [(&str, f32); 3] = [
+    ("settings.zoom_slow", 100.0),
+    ("settings.zoom_normal", 50.0),
+    ("settings.zoom_fast", 25.0),
+];
+const WINDOW_SIZES: [(u32, u32); 3] = [(600, 600), (800, 800), (1000, 1000)];
+
+/// Pushed on top of the start screen or the menu, every change takes effect straight away and
+/// is remembered for next time
+#[derive(Default, Debug)]
+pub struct Settings {
+    ui_root: Option<Entity>,
+    navigation: MenuNavigation,
+    button_volume: Option<Entity>,
+    button_camera: Option<Entity>,
+    button_zoom: Option<Entity>,
+    button_hud: Option<Entity>,
+    button_window: Option<Entity>,
+    button_language: Option<Entity>,
+    button_controls: Option<Entity>,
+    button_back: Option<Entity>,
+    label_volume: Option<Entity>,
+    label_camera: Option<Entity>,
+    label_zoom: Option<Entity>,
+    label_hud: Option<Entity>,
+    label_window: Option<Entity>,
+    label_language: Option<Entity>,
+}
+
+impl State<GameData<'static, 'static>, GameStateEvent> for Settings {
+    fn on_start(&mut self, state_data: StateData<'_, GameData<'_, '_>>) {
+        let StateData { world, .. } = state_data;
+
+        self.ui_root =
+            Some(world.exec(|mut creator: UiCreator<'_>| creator.create("ui/settings.ron", ())));
+    }
+
+    fn on_pause(&mut self, state_data: StateData<'_, GameData<'_, '_>>) {
+        hide_ui(state_data.world, self.ui_root);
+    }
+
+    fn on_resume(&mut self, state_data: StateData<'_, GameData<'_, '_>>) {
+        show_ui(state_data.world, self.ui_root);
+    }
+
+    fn update(&mut self, state_data: StateData<'_, GameData<'_, '_>>) -> GameTrans {
+        let StateData { world, data } = state_data;
+        data.update(world);
+        self.navigation.find(world, self.ui_root);
+
+        if self.button_back.is_none() || self.label_language.is_none() {
+            world.exec(|ui_finder: UiFinder<'_>| {
+                self.button_volume = ui_finder.find(BUTTON_VOLUME);
+                self.button_camera = ui_finder.find(BUTTON_CAMERA);
+                self.button_zoom = ui_finder.find(BUTTON_ZOOM);
+                self.button_hud = ui_finder.find(BUTTON_HUD);
+                self.button_window = ui_finder.find(BUTTON_WINDOW);
+                self.button_language = ui_finder.find(BUTTON_LANGUAGE);
+                self.button_controls = ui_finder.find(BUTTON_CONTROLS);
+                self.button_back = ui_finder.find(BUTTON_BACK);
+                self.label_volume = ui_finder.find(LABEL_VOLUME);
+                self.label_camera = ui_finder.find(LABEL_CAMERA);
+                self.label_zoom = ui_finder.find(LABEL_ZOOM);
+                self.label_hud = ui_finder.find(LABEL_HUD);
+                self.label_window = ui_finder.find(LABEL_WINDOW);
+                self.label_language = ui_finder.find(LABEL_LANGUAGE);
+            });
+        }
+
+        let values = {
+            let config = world.read_resource::<WanderballConfig>();
+            let preferences = world.read_resource::<Preferences>();
+            let locale = world.read_resource::<Locale>();
+            [
+                (
+                    self.label_volume,
+                    format!("{:.0}%", config.music_volume * 100.0),
+                ),
+                (
+                    self.label_camera,
+                    locale.text(match config.camera_mode {
+                        CameraMode::Flip => "settings.camera_flip",
+                        CameraMode::Follow => "settings.camera_follow",
+                    }),
+                ),
+                (
+                    self.label_zoom,
+                    ZOOM_SPEEDS
+                        .iter()
+                        .find(|(_, factor)| *factor == config.zoom_factor)
+                        .map_or_else(
+                            || locale.text("settings.zoom_custom"),
+                            |(key, _)| locale.text(key),
+                        ),
+                ),
+                (
+                    self.label_hud,
+                    locale.text(if config.show_hud {
+                        "settings.on"
+                    } else {
+                        "settings.off"
+                    }),
+                ),
+                (
+                    self.label_window,
+                    preferences.window_size.map_or_else(
+                        || locale.text("settings.window_default"),
+                        |(width, height)| format!("{}x{}", width, height),
+                    ),
+                ),
+                (self.label_language, locale.name.clone()),
+            ]
+        };
+
+        let mut ui_text = world.write_storage::<UiText>();
+        for (label, value) in values.iter() {
+            if let Some(text) = label.and_then(|label| ui_text.get_mut(label)) {
+                text.text = value.clone();
+            }
+        }
+
+        Trans::None
+    }
+
+    fn handle_event(
+        &mut self,
+        state_data: StateData<'_, GameData<'_, '_>>,
+        event: GameStateEvent,
+    ) -> GameTrans {
+        let StateData { world, .. } = state_data;
+
+        match event {
+            StateEvent::Window(event) => {
+                if self.navigation.handle_event(world, &event) {
+                    Trans::None
+                } else if is_close_requested(&event) {
+                    Trans::Quit
+                } else if is_key_down(&event, VirtualKeyCode::Escape) {
+                    Trans::Pop
+                } else {
+                    Trans::None
+                }
+            }
+            StateEvent::Ui(UiEvent {
+                event_type: UiEventType::Click,
+                target,
+            }) => {
+                let target = Some(target);
+
+                if target == self.button_back {
+                    log::info!("[Trans::Pop] closing settings");
+                    return Trans::Pop;
+                }
+                if target == self.button_controls {
+                    log::info!("[Trans::Push] key bindings");
+                    return Trans::Push(Box::new(KeyBindings::default()));
+                }
+
+                if target == self.button_volume {
+                    let volume = {
+                        let mut config = world.write_resource::<WanderballConfig>();
+                        let steps = (config.music_volume / VOLUME_STEP).round() + 1.0;
+                        config.music_volume = if steps * VOLUME_STEP > 1.0 + f32::EPSILON {
+                            0.0
+                        } else {
+                            steps * VOLUME_STEP
+                        };
+                        config.music_volume
+                    };
+                    set_music_volume(world, volume);
+                    world.write_resource::<Preferences>().music_volume = Some(volume);
+                } else if target == self.button_camera {
+                    let mut config = world.write_resource::<WanderballConfig>();
+                    config.camera_mode = match config.camera_mode {
+                        CameraMode::Flip => CameraMode::Follow,
+                        CameraMode::Follow => CameraMode::Flip,
+                    };
+                    world.write_resource::<Preferences>().camera_mode = Some(config.camera_mode);
+                } else if target == self.button_zoom {
+                    let mut config = world.write_resource::<WanderballConfig>();
+                    let next = ZOOM_SPEEDS
+                        .iter()
+                        .position(|(_, factor)| *factor == config.zoom_factor)
+                        .map_or(0, |index| (index + 1) % ZOOM_SPEEDS.len());
+                    config.zoom_factor = ZOOM_SPEEDS[next].1;
+                    world.write_resource::<Preferences>().zoom_factor = Some(config.zoom_factor);
+                } else if target == self.button_hud {
+                    let mut config = world.write_resource::<WanderballConfig>();
+                    config.show_hud = !config.show_hud;
+                    world.write_resource::<Preferences>().show_hud = Some(config.show_hud);
+                } else if target == self.button_window {
+                    let mut preferences = world.write_resource::<Preferences>();
+                    let next = preferences
+                        .window_size
+                        .and_then(|size| WINDOW_SIZES.iter().position(|other| *other == size))
+                        .map_or(0, |index| (index + 1) % WINDOW_SIZES.len());
+                    let (width, height) = WINDOW_SIZES[next];
+                    world
+                        .read_resource::<Window>()
+                        .set_inner_size(LogicalSize::new(width.into(), height.into()));
+                    preferences.window_size = Some((width, height));
+                } else if target == self.button_language {
+                    let mut locale = world.write_resource::<Locale>();
+                    let languages = locale.languages();
+                    let next = languages
+                        .iter()
+                        .position(|language| *language == locale.language)
+                        .map_or(0, |index| (index + 1) % languages.len());
+                    if let Some(language) = languages.get(next) {
+                        locale.set_language(language);
+                        world.write_resource::<Preferences>().language = Some(language.clone());
+                    }
+                } else {
+                    return Trans::None;
+                }
+
+                write_preferences(&world.read_resource::<Preferences>());
+                Trans::None
+            }
+            _ => Trans::None,
+        }
+    }
+
+    fn on_stop(&mut self, data: StateData<GameData>) {
+        if let Some(root_entity) = self.ui_root {
+            data.world
+                .delete_entity(root_entity)
+                .expect("failed to close settings");
+        }
+
+        self.ui_root = None;
+        self.navigation = MenuNavigation::default();
+        self.button_volume = None;
+        self.button_camera = None;
+        self.button_zoom = None;
+        self.button_hud = None;
+        self.button_window = None;
+        self.button_language = None;
+        self.button_controls = None;
+        self.button_back = None;
+        self.label_volume = None;
+        self.label_camera = None;
+        self.label_zoom = None;
+        self.label_hud = None;
+        self.label_window = None;
+        self.label_language = None;
+    }
+}
+
+/// Keeps the screen underneath from showing through while the settings are open
+pub fn hide_ui(world: &mut World, ui_root: Option<Entity>) {
+    if let Some(ui_root) = ui_root {
+        let _ = world
+            .write_storage::<HiddenPropagate>()
+            .insert(ui_root, HiddenPropagate::new());
+    }
+}
+
+pub fn show_ui(world: &mut World, ui_root: Option<Entity>) {
+    if let Some(ui_root) = ui_root {
+        world.write_storage::<HiddenPropagate>().remove(ui_root);
+    }
+}
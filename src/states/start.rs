@@ -6,23 +6,31 @@ use amethyst::{
 };
 
 use crate::audio::initialize_audio;
-use crate::components::wanderdata::{CoordinateText, PedometerText};
 use crate::states::game::Wanderball;
-use crate::states::loading::Loading;
+use crate::states::load_game::LoadGame;
+use crate::states::navigation::MenuNavigation;
+use crate::states::replay::Replay;
+use crate::states::settings::{hide_ui, show_ui, Settings};
+use crate::states::{GameStateEvent, GameTrans};
 
 const BUTTON_START: &str = "start";
 const BUTTON_LOAD: &str = "load";
+const BUTTON_REPLAY: &str = "replay";
+const BUTTON_SETTINGS: &str = "settings";
 const BUTTON_QUIT: &str = "quit";
 
 #[derive(Default, Debug)]
 pub struct StartScreen {
     ui_root: Option<Entity>,
+    navigation: MenuNavigation,
     button_start: Option<Entity>,
     button_load: Option<Entity>,
+    button_replay: Option<Entity>,
+    button_settings: Option<Entity>,
     button_quit: Option<Entity>,
 }
 
-impl SimpleState for StartScreen {
+impl State<GameData<'static, 'static>, GameStateEvent> for StartScreen {
     fn on_start(&mut self, state_data: StateData<'_, GameData<'_, '_>>) {
         let StateData { world, .. } = state_data;
 
@@ -30,21 +38,32 @@ impl SimpleState for StartScreen {
             Some(world.exec(|mut creator: UiCreator<'_>| creator.create("ui/start.ron", ())));
 
         initialize_audio(world);
-        let noop_coordinates = world.create_entity().build();
-        let noop_steps = world.create_entity().build();
-        world.insert(CoordinateText {
-            coordinates: noop_coordinates,
-        });
-        world.insert(PedometerText { steps: noop_steps });
     }
 
-    fn update(&mut self, state_data: &mut StateData<'_, GameData<'_, '_>>) -> SimpleTrans {
-        let StateData { world, .. } = state_data;
+    fn on_pause(&mut self, state_data: StateData<'_, GameData<'_, '_>>) {
+        hide_ui(state_data.world, self.ui_root);
+    }
+
+    fn on_resume(&mut self, state_data: StateData<'_, GameData<'_, '_>>) {
+        show_ui(state_data.world, self.ui_root);
+    }
 
-        if self.button_start.is_none() || self.button_load.is_none() || self.button_quit.is_none() {
+    fn update(&mut self, state_data: StateData<'_, GameData<'_, '_>>) -> GameTrans {
+        let StateData { world, data } = state_data;
+        data.update(world);
+        self.navigation.find(world, self.ui_root);
+
+        if self.button_start.is_none()
+            || self.button_load.is_none()
+            || self.button_replay.is_none()
+            || self.button_settings.is_none()
+            || self.button_quit.is_none()
+        {
             world.exec(|ui_finder: UiFinder<'_>| {
                 self.button_start = ui_finder.find(BUTTON_START);
                 self.button_load = ui_finder.find(BUTTON_LOAD);
+                self.button_replay = ui_finder.find(BUTTON_REPLAY);
+                self.button_settings = ui_finder.find(BUTTON_SETTINGS);
                 self.button_quit = ui_finder.find(BUTTON_QUIT);
             });
         }
@@ -54,12 +73,14 @@ impl SimpleState for StartScreen {
 
     fn handle_event(
         &mut self,
-        _: StateData<'_, GameData<'_, '_>>,
-        event: StateEvent,
-    ) -> SimpleTrans {
+        state_data: StateData<'_, GameData<'_, '_>>,
+        event: GameStateEvent,
+    ) -> GameTrans {
         match event {
             StateEvent::Window(event) => {
-                if is_close_requested(&event) {
+                if self.navigation.handle_event(state_data.world, &event) {
+                    Trans::None
+                } else if is_close_requested(&event) {
                     Trans::Quit
                 } else {
                     Trans::None
@@ -74,8 +95,16 @@ impl SimpleState for StartScreen {
                     return Trans::Switch(Box::new(Wanderball::default()));
                 }
                 if Some(target) == self.button_load {
-                    log::info!("[Trans::None] load game");
-                    return Trans::Switch(Box::new(Loading::default()));
+                    log::info!("[Trans::Push] load game");
+                    return Trans::Push(Box::new(LoadGame::default()));
+                }
+                if Some(target) == self.button_replay {
+                    log::info!("[Trans::Switch] replay last game");
+                    return Trans::Switch(Box::new(Replay::default()));
+                }
+                if Some(target) == self.button_settings {
+                    log::info!("[Trans::Push] settings");
+                    return Trans::Push(Box::new(Settings::default()));
                 }
                 if Some(target) == self.button_quit {
                     log::info!("[Trans::Quit] quit game");
@@ -96,8 +125,11 @@ impl SimpleState for StartScreen {
         }
 
         self.ui_root = None;
+        self.navigation = MenuNavigation::default();
         self.button_start = None;
         self.button_load = None;
+        self.button_replay = None;
+        self.button_settings = None;
         self.button_quit = None;
     }
 }
@@ -0,0 +1,294 @@
+use amethyst::{
+    core::Parent,
+    ecs::prelude::Entity,
+    input::{is_close_requested, is_key_down},
+    prelude::*,
+    ui::{Anchor, UiCreator, UiEvent, UiEventType, UiFinder, UiImage, UiText, UiTransform},
+    winit::VirtualKeyCode,
+};
+
+use crate::components::collectible::Collection;
+use crate::components::wanderdata::{format_duration, Pedometer};
+use crate::resources::locale::Locale;
+use crate::resources::saves::UnsavedChanges;
+use crate::resources::session::Session;
+use crate::states::menu::Menu;
+use crate::states::navigation::MenuNavigation;
+use crate::states::saving::Saving;
+use crate::states::{GameStateEvent, GameTrans};
+
+const LABEL_STATS: &str = "summary_stats";
+const LABEL_MESSAGE: &str = "summary_message";
+const MAP: &str = "summary_map";
+const BUTTON_SAVE_AND_QUIT: &str = "summary_save_and_quit";
+const BUTTON_QUIT: &str = "summary_quit";
+const BUTTON_BACK: &str = "summary_back";
+
+/// The route's drawn with at most this many dots
+const MAP_DOTS: usize = 300;
+const MAP_MARGIN: f32 = 10.0;
+const DOT_SIZE: f32 = 3.0;
+const END_SIZE: f32 = 7.0;
+const ROUTE_COLOR: [f32; 4] = [0.7, 0.7, 0.7, 1.0];
+const START_COLOR: [f32; 4] = [0.2, 1.0, 0.2, 1.0];
+const FINISH_COLOR: [f32; 4] = [0.2, 0.2, 1.0, 1.0];
+
+/// Shown on the way out of the game so you can see what you got up to this session, with a map
+/// of where the ball went. Quitting from here can save first, and if the save doesn't work it
+/// stays put so nothing's lost.
+#[derive(Default, Debug)]
+pub struct Summary {
+    ui_root: Option<Entity>,
+    navigation: MenuNavigation,
+    label_stats: Option<Entity>,
+    label_message: Option<Entity>,
+    map: Option<Entity>,
+    button_save_and_quit: Option<Entity>,
+    button_quit: Option<Entity>,
+    button_back: Option<Entity>,
+    /// the dots making up the route on the map
+    dots: Vec<Entity>,
+    drawn: bool,
+    /// waiting to hear back from saving before quitting
+    saving: bool,
+    saved: bool,
+    message: String,
+}
+
+impl Summary {
+    /// Scales the session's route to fit the map and puts a dot on it for each point, the start
+    /// and where the ball is now get bigger ones
+    fn draw_map(&mut self, world: &mut World, map: Entity) {
+        let (width, height) = match world.read_storage::<UiTransform>().get(map) {
+            Some(transform) => (transform.width, transform.height),
+            None => return,
+        };
+        self.drawn = true;
+
+        let route = world
+            .try_fetch::<Session>()
+            .map(|session| session.route.clone())
+            .unwrap_or_default();
+        let (first, last) = match (route.first(), route.last()) {
+            (Some(first), Some(last)) => (*first, *last),
+            _ => return,
+        };
+
+        let (min_x, max_x, min_y, max_y) = route.iter().fold(
+            (first.0, first.0, first.1, first.1),
+            |(min_x, max_x, min_y, max_y), (x, y)| {
+                (min_x.min(*x), max_x.max(*x), min_y.min(*y), max_y.max(*y))
+            },
+        );
+        let span = (max_x - min_x).max(max_y - min_y).max(1.0);
+        let scale = (width.min(height) - MAP_MARGIN * 2.0) / span;
+        let centre = ((min_x + max_x) / 2.0, (min_y + max_y) / 2.0);
+
+        let every = (route.len() + MAP_DOTS - 1) / MAP_DOTS;
+        let dots = route
+            .iter()
+            .step_by(every.max(1))
+            .map(|point| (*point, ROUTE_COLOR, DOT_SIZE))
+            .chain(vec![
+                (first, START_COLOR, END_SIZE),
+                (last, FINISH_COLOR, END_SIZE),
+            ]);
+
+        for (index, ((x, y), color, size)) in dots.enumerate() {
+            let dot = world
+                .create_entity()
+                .with(UiTransform::new(
+                    format!("{}_{}", MAP, index),
+                    Anchor::Middle,
+                    Anchor::Middle,
+                    (x - centre.0) * scale,
+                    (y - centre.1) * scale,
+                    1.0,
+                    size,
+                    size,
+                ))
+                .with(UiImage::SolidColor(color))
+                .with(Parent { entity: map })
+                .build();
+            self.dots.push(dot);
+        }
+    }
+}
+
+impl State<GameData<'static, 'static>, GameStateEvent> for Summary {
+    fn on_start(&mut self, state_data: StateData<'_, GameData<'_, '_>>) {
+        let StateData { world, .. } = state_data;
+
+        self.ui_root =
+            Some(world.exec(|mut creator: UiCreator<'_>| creator.create("ui/summary.ron", ())));
+    }
+
+    fn on_resume(&mut self, state_data: StateData<'_, GameData<'_, '_>>) {
+        if !self.saving {
+            return;
+        }
+        self.saving = false;
+
+        // saving clears this once the save's been written
+        if state_data.world.read_resource::<UnsavedChanges>().dirty {
+            log::error!("save failed, not quitting");
+            self.message = state_data
+                .world
+                .read_resource::<Locale>()
+                .text("summary.save_failed");
+        } else {
+            self.saved = true;
+        }
+    }
+
+    fn update(&mut self, state_data: StateData<'_, GameData<'_, '_>>) -> GameTrans {
+        let StateData { world, data } = state_data;
+        data.update(world);
+
+        if self.saved {
+            log::info!("[Trans::Quit] saved, quitting game");
+            return Trans::Quit;
+        }
+
+        self.navigation.find(world, self.ui_root);
+
+        if self.label_stats.is_none()
+            || self.label_message.is_none()
+            || self.map.is_none()
+            || self.button_save_and_quit.is_none()
+            || self.button_quit.is_none()
+            || self.button_back.is_none()
+        {
+            world.exec(|ui_finder: UiFinder<'_>| {
+                self.label_stats = ui_finder.find(LABEL_STATS);
+                self.label_message = ui_finder.find(LABEL_MESSAGE);
+                self.map = ui_finder.find(MAP);
+                self.button_save_and_quit = ui_finder.find(BUTTON_SAVE_AND_QUIT);
+                self.button_quit = ui_finder.find(BUTTON_QUIT);
+                self.button_back = ui_finder.find(BUTTON_BACK);
+            });
+        }
+
+        if let Some(map) = self.map.filter(|_| !self.drawn) {
+            self.draw_map(world, map);
+        }
+
+        if let Some(label_stats) = self.label_stats {
+            let summary = summarize_session(
+                &world.read_resource::<Session>(),
+                &world.read_resource::<Pedometer>(),
+                &world.read_resource::<Locale>(),
+            );
+            if let Some(text) = world.write_storage::<UiText>().get_mut(label_stats) {
+                text.text = summary;
+            }
+        }
+
+        if let Some(label_message) = self.label_message {
+            if let Some(text) = world.write_storage::<UiText>().get_mut(label_message) {
+                text.text = self.message.clone();
+            }
+        }
+
+        Trans::None
+    }
+
+    fn handle_event(
+        &mut self,
+        state_data: StateData<'_, GameData<'_, '_>>,
+        event: GameStateEvent,
+    ) -> GameTrans {
+        match event {
+            StateEvent::Window(event) => {
+                if self.navigation.handle_event(state_data.world, &event) {
+                    Trans::None
+                } else if is_close_requested(&event) {
+                    Trans::Quit
+                } else if is_key_down(&event, VirtualKeyCode::Escape) {
+                    Trans::Switch(Box::new(Menu::default()))
+                } else {
+                    Trans::None
+                }
+            }
+            StateEvent::Ui(UiEvent {
+                event_type: UiEventType::Click,
+                target,
+            }) => {
+                if Some(target) == self.button_save_and_quit {
+                    log::info!("[Trans::Push] save, then quit game");
+                    // whatever's on screen gets saved even if nothing's moved since the last
+                    // save, so the flag only clears if this save works
+                    state_data.world.write_resource::<UnsavedChanges>().dirty = true;
+                    self.saving = true;
+                    self.message = String::new();
+                    return Trans::Push(Box::new(Saving::default()));
+                }
+                if Some(target) == self.button_quit {
+                    log::info!("[Trans::Quit] quit game without saving");
+                    return Trans::Quit;
+                }
+                if Some(target) == self.button_back {
+                    log::info!("[Trans::Switch] back to menu");
+                    return Trans::Switch(Box::new(Menu::default()));
+                }
+
+                Trans::None
+            }
+            _ => Trans::None,
+        }
+    }
+
+    fn on_stop(&mut self, data: StateData<GameData>) {
+        for dot in self.dots.drain(..) {
+            let _ = data.world.delete_entity(dot);
+        }
+        if let Some(root_entity) = self.ui_root {
+            data.world
+                .delete_entity(root_entity)
+                .expect("failed to close summary");
+        }
+
+        self.ui_root = None;
+        self.navigation = MenuNavigation::default();
+        self.label_stats = None;
+        self.label_message = None;
+        self.map = None;
+        self.button_save_and_quit = None;
+        self.button_quit = None;
+        self.button_back = None;
+        self.drawn = false;
+        self.saving = false;
+        self.saved = false;
+        self.message = String::new();
+    }
+}
+
+/// What happened since the game was started or loaded
+pub fn summarize_session(session: &Session, pedometer: &Pedometer, locale: &Locale) -> String {
+    locale.format(
+        "summary.session",
+        &[
+            &session.steps(pedometer),
+            &format!("{:.0}", session.distance(pedometer)),
+            &format_duration(session.time_played(pedometer)),
+            &session.discovered(pedometer),
+            &format!("{:.1}", pedometer.exploration()),
+        ],
+    )
+}
+
+pub fn summarize(pedometer: &Pedometer, collection: &Collection, locale: &Locale) -> String {
+    locale.format(
+        "summary.stats",
+        &[
+            &pedometer.steps,
+            &format!("{:.1}", pedometer.exploration()),
+            &format!("{:.0}", pedometer.distance),
+            &format_duration(pedometer.time_played),
+            &pedometer.longest_streak,
+            &format!("{:.1}", pedometer.segments_per_minute()),
+            &collection.collected,
+            &collection.total,
+        ],
+    )
+}
@@ -0,0 +1,83 @@
+use amethyst::{
+    core::Transform,
+    derive::SystemDesc,
+    ecs::{Join, Read, ReadStorage, System, SystemData, Write},
+};
+
+use crate::autopilot::Autopilot;
+use crate::components::{ball::Ball, gate::Inventory, player::Player, shapes::circle::Circle};
+use crate::config::WanderballConfig;
+use crate::resources::{
+    controls::Controls, graph::SegmentGraph, pause::Pause, save::PathSegmentRecord,
+};
+
+/// Takes over the first player's movement when the autopilot's on, everything else about the
+/// controls is left as the keyboard had it
+#[derive(SystemDesc)]
+pub struct AutopilotSystem;
+
+impl<'s> System<'s> for AutopilotSystem {
+    type SystemData = (
+        Write<'s, Autopilot>,
+        Write<'s, Controls>,
+        ReadStorage<'s, Transform>,
+        ReadStorage<'s, Circle>,
+        ReadStorage<'s, Ball>,
+        ReadStorage<'s, Player>,
+        Read<'s, SegmentGraph>,
+        Read<'s, Vec<PathSegmentRecord>>,
+        Read<'s, Inventory>,
+        Read<'s, Pause>,
+        Read<'s, WanderballConfig>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            mut autopilot,
+            mut controls,
+            transforms,
+            circles,
+            balls,
+            players,
+            graph,
+            path_segments,
+            inventory,
+            pause,
+            config,
+        ): Self::SystemData,
+    ) {
+        if !autopilot.active || pause.paused {
+            return;
+        }
+
+        // gates still waiting on their key are as closed to the autopilot as to anyone
+        let open = |segment: &PathSegmentRecord| inventory.opens(segment);
+
+        if let Some((transform, circle, _, _)) = (&transforms, &circles, &balls, &players)
+            .join()
+            .find(|(_, _, _, player)| player.index == 0)
+        {
+            let movement = autopilot.steer(
+                &graph,
+                &path_segments,
+                transform.translation().x,
+                transform.translation().y,
+                circle,
+                config.autopilot_stuck_frames,
+                open,
+            );
+
+            if controls.movement.is_empty() {
+                controls.movement.push(movement);
+            } else {
+                controls.movement[0] = movement;
+            }
+
+            if autopilot.finished {
+                log::info!("autopilot finished exploring");
+                autopilot.active = false;
+            }
+        }
+    }
+}
@@ -1,19 +1,137 @@
 use amethyst::{
     core::Transform,
     derive::SystemDesc,
-    ecs::{Join, Read, ReadExpect, ReadStorage, System, SystemData, Write, WriteStorage},
-    input::{InputHandler, StringBindings},
-    ui::UiText,
+    ecs::{Join, Read, ReadStorage, System, SystemData, Write, WriteStorage},
 };
 
 use crate::components::{
     ball::Ball,
+    gate::Inventory,
+    path::segment_key,
+    player::Player,
     shapes::circle::Circle,
     shapes::rectangle::point_in_rect,
-    wanderdata::{Pedometer, PedometerText},
+    surface::{surface_at, Momentum},
+    wanderdata::Pedometer,
 };
+use crate::config::WanderballConfig;
 
-use crate::resources::save::PathSegmentRecord;
+use crate::resources::{
+    controls::{Controls, Movement},
+    save::PathSegmentRecord,
+    saves::UnsavedChanges,
+};
+
+/// Where a ball ended up after trying to move, and which segments it rolled onto on the way
+#[derive(Debug, Clone, Default)]
+pub struct Roll {
+    pub x: f32,
+    pub y: f32,
+    pub distance: f32,
+    pub stepped_on: Vec<String>,
+}
+
+/// Moves a ball as far as the path lets it, first across and then up or down, only over the
+/// segments `open` lets it onto. Kept apart from the system so the explorer can drive a ball
+/// around without a world.
+pub fn roll<F>(
+    x: f32,
+    y: f32,
+    circle: &Circle,
+    movement: Movement,
+    path_segments: &[PathSegmentRecord],
+    open: F,
+) -> Roll
+where
+    F: Fn(&PathSegmentRecord) -> bool,
+{
+    let mut roll = Roll {
+        x,
+        y,
+        ..Default::default()
+    };
+
+    let left = |segment: &PathSegmentRecord| -> f32 {
+        segment.transform.translation().x - (segment.rectangle.width * 0.5) + circle.radius
+    };
+    let bottom = |segment: &PathSegmentRecord| -> f32 {
+        segment.transform.translation().y - (segment.rectangle.height * 0.5) + circle.radius
+    };
+    let right = |segment: &PathSegmentRecord| -> f32 {
+        segment.transform.translation().x + (segment.rectangle.width * 0.5) - circle.radius
+    };
+    let top = |segment: &PathSegmentRecord| -> f32 {
+        segment.transform.translation().y + (segment.rectangle.height * 0.5) - circle.radius
+    };
+
+    if let Some(mv_amount) = movement.move_x {
+        if mv_amount.abs() > f32::EPSILON {
+            let mut threshold = circle.radius * 2.0;
+            if mv_amount < 0.0 {
+                threshold = -threshold;
+            }
+            let new_x = roll.x + mv_amount;
+            for segment in path_segments.iter().filter(|segment| open(segment)) {
+                if point_in_rect(
+                    new_x,
+                    roll.y,
+                    left(segment),
+                    bottom(segment),
+                    right(segment),
+                    top(segment),
+                ) || point_in_rect(
+                    new_x + threshold,
+                    roll.y,
+                    left(segment),
+                    bottom(segment),
+                    right(segment),
+                    top(segment),
+                ) {
+                    roll.x = new_x;
+                    roll.distance += mv_amount.abs();
+                    roll.stepped_on.push(segment_key(&segment.transform));
+
+                    break;
+                }
+            }
+        }
+    }
+
+    if let Some(mv_amount) = movement.move_y {
+        if mv_amount.abs() > f32::EPSILON {
+            let mut threshold = circle.radius * 2.0;
+            if mv_amount < 0.0 {
+                threshold = -threshold;
+            }
+            let new_y = roll.y + mv_amount;
+            for segment in path_segments.iter().filter(|segment| open(segment)) {
+                if point_in_rect(
+                    roll.x,
+                    new_y,
+                    left(segment),
+                    bottom(segment),
+                    right(segment),
+                    top(segment),
+                ) || point_in_rect(
+                    roll.x,
+                    new_y + threshold,
+                    left(segment),
+                    bottom(segment),
+                    right(segment),
+                    top(segment),
+                ) {
+                    roll.y = new_y;
+                    roll.distance += mv_amount.abs();
+                    roll.stepped_on.push(segment_key(&segment.transform));
+
+                    break;
+                }
+            }
+        }
+    }
+
+    roll
+}
 
 #[derive(SystemDesc)]
 pub struct BallSystem;
@@ -23,11 +141,15 @@ impl<'s> System<'s> for BallSystem {
         WriteStorage<'s, Transform>,
         ReadStorage<'s, Ball>,
         ReadStorage<'s, Circle>,
+        ReadStorage<'s, Player>,
+        WriteStorage<'s, Pedometer>,
         Write<'s, Pedometer>,
-        ReadExpect<'s, PedometerText>,
-        WriteStorage<'s, UiText>,
         Read<'s, Vec<PathSegmentRecord>>,
-        Read<'s, InputHandler<StringBindings>>,
+        Read<'s, Controls>,
+        WriteStorage<'s, Momentum>,
+        Read<'s, Inventory>,
+        Write<'s, UnsavedChanges>,
+        Read<'s, WanderballConfig>,
     );
 
     fn run(
@@ -36,115 +158,58 @@ impl<'s> System<'s> for BallSystem {
             mut transforms,
             balls,
             circles,
+            players,
+            mut player_pedometers,
             mut pedometer,
-            pedometer_text,
-            mut ui_text,
             path_segments,
-            input,
+            controls,
+            mut momentums,
+            inventory,
+            mut unsaved,
+            config,
         ): Self::SystemData,
     ) {
-        let movement_x = input.axis_value("move_x");
-        let movement_y = input.axis_value("move_y");
-        for (transform, circle, _) in (&mut transforms, &circles, &balls).join() {
-            let left = |segment: &PathSegmentRecord| -> f32 {
-                segment.transform.translation().x - (segment.rectangle.width * 0.5) + circle.radius
-            };
-            let bottom = |segment: &PathSegmentRecord| -> f32 {
-                segment.transform.translation().y - (segment.rectangle.height * 0.5) + circle.radius
-            };
-            let right = |segment: &PathSegmentRecord| -> f32 {
-                segment.transform.translation().x + (segment.rectangle.width * 0.5) - circle.radius
-            };
-            let top = |segment: &PathSegmentRecord| -> f32 {
-                segment.transform.translation().y + (segment.rectangle.height * 0.5) - circle.radius
-            };
-            if let Some(mv_amount) = movement_x {
-                if mv_amount.floor() as i32 != 0 {
-                    let mut threshold = circle.radius * 2.0;
-                    if mv_amount < 0.0 {
-                        threshold = -threshold;
-                    }
-                    let new_x = transform.translation().x + (mv_amount as f32);
-                    for segment in &(*path_segments) {
-                        if point_in_rect(
-                            new_x,
-                            transform.translation().y,
-                            left(segment),
-                            bottom(segment),
-                            right(segment),
-                            top(segment),
-                        ) || point_in_rect(
-                            new_x + threshold,
-                            transform.translation().y,
-                            left(segment),
-                            bottom(segment),
-                            right(segment),
-                            top(segment),
-                        ) {
-                            transform.set_translation_x(new_x);
-
-                            let point = format!(
-                                "{}{}",
-                                segment.transform.translation().x,
-                                segment.transform.translation().y
-                            );
-
-                            if !pedometer.visited.contains_key(&point) {
-                                pedometer.steps += 1;
-                                pedometer.visited.insert(point.clone(), ());
-                            }
-
-                            break;
-                        }
-                    }
-                }
-            }
+        // gates still waiting on their key are left out so there's no rolling onto them
+        let open = |segment: &PathSegmentRecord| inventory.opens(segment);
 
-            if let Some(mv_amount) = movement_y {
-                if mv_amount.floor() as i32 != 0 {
-                    let mut threshold = circle.radius * 2.0;
-                    if mv_amount < 0.0 {
-                        threshold = -threshold;
-                    }
-                    let new_y = transform.translation().y + (mv_amount as f32);
-                    for segment in &(*path_segments) {
-                        if point_in_rect(
-                            transform.translation().x,
-                            new_y,
-                            left(segment),
-                            bottom(segment),
-                            right(segment),
-                            top(segment),
-                        ) || point_in_rect(
-                            transform.translation().x,
-                            new_y + threshold,
-                            left(segment),
-                            bottom(segment),
-                            right(segment),
-                            top(segment),
-                        ) {
-                            transform.set_translation_y(new_y);
-
-                            let point = format!(
-                                "{}{}",
-                                segment.transform.translation().x,
-                                segment.transform.translation().y
-                            );
-
-                            if !pedometer.visited.contains_key(&point) {
-                                pedometer.steps += 1;
-                                pedometer.visited.insert(point.clone(), ());
-                            }
-
-                            break;
-                        }
-                    }
-                }
-            }
+        for (transform, circle, player, player_pedometer, momentum, _) in (
+            &mut transforms,
+            &circles,
+            &players,
+            &mut player_pedometers,
+            &mut momentums,
+            &balls,
+        )
+            .join()
+        {
+            let x = transform.translation().x;
+            let y = transform.translation().y;
+            let wanted = controls.movement(player.index);
+            let movement = surface_at(x, y, &path_segments, open).apply(wanted, momentum, &config);
+            let roll = roll(x, y, circle, movement, &path_segments, open);
 
-            if let Some(text) = ui_text.get_mut(pedometer_text.steps) {
-                text.text = format!("(path steps: {})", pedometer.steps.to_string());
+            transform.set_translation_x(roll.x);
+            transform.set_translation_y(roll.y);
+            player_pedometer.distance += roll.distance;
+            // anywhere a ball rolls is progress that would be lost without saving
+            if roll.distance > 0.0 {
+                unsaved.dirty = true;
+            }
+            for key in roll.stepped_on {
+                player_pedometer.step_on(key);
             }
         }
+
+        // the totals are only ever worked out from the players' own, first player first
+        let mut per_player: Vec<(usize, &Pedometer)> = (&players, &player_pedometers)
+            .join()
+            .map(|(player, player_pedometer)| (player.index, player_pedometer))
+            .collect();
+        per_player.sort_by_key(|(index, _)| *index);
+        let per_player: Vec<&Pedometer> = per_player
+            .into_iter()
+            .map(|(_, player_pedometer)| player_pedometer)
+            .collect();
+        pedometer.combine(&per_player);
     }
 }
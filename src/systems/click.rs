@@ -0,0 +1,181 @@
+use amethyst::{
+    core::{transform::Transform, Parent},
+    derive::SystemDesc,
+    ecs::{Join, Read, ReadExpect, ReadStorage, System, SystemData, Write},
+    input::InputHandler,
+    renderer::Camera,
+    window::ScreenDimensions,
+    winit::MouseButton,
+};
+
+use crate::camera::screen_to_world;
+use crate::components::{
+    ball::Ball, fog::FogOfWar, gate::Inventory, path::segment_key, player::Player,
+};
+use crate::resources::{
+    bindings::GameBindings,
+    controls::{Controls, Playback},
+    graph::SegmentGraph,
+    pause::Pause,
+    route::Route,
+    save::PathSegmentRecord,
+};
+use std::collections::HashSet;
+
+/// Turns a left click on a segment the fog's lifted from into a route for the first player's
+/// ball to roll along, anything pressed on the keyboard calls it off
+#[derive(SystemDesc)]
+pub struct ClickSystem;
+
+impl<'s> System<'s> for ClickSystem {
+    type SystemData = (
+        Write<'s, Route>,
+        Read<'s, InputHandler<GameBindings>>,
+        ReadStorage<'s, Camera>,
+        ReadStorage<'s, Parent>,
+        ReadStorage<'s, Transform>,
+        ReadStorage<'s, Ball>,
+        ReadStorage<'s, Player>,
+        Read<'s, SegmentGraph>,
+        Read<'s, FogOfWar>,
+        Read<'s, Vec<PathSegmentRecord>>,
+        Read<'s, Inventory>,
+        Read<'s, Playback>,
+        Read<'s, Pause>,
+        ReadExpect<'s, ScreenDimensions>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            mut route,
+            input,
+            cameras,
+            parents,
+            transforms,
+            balls,
+            players,
+            graph,
+            fog,
+            path_segments,
+            inventory,
+            playback,
+            pause,
+            screen,
+        ): Self::SystemData,
+    ) {
+        // a click that closed the menu is still held down once the game's back
+        let was_clicking = route.clicking;
+        route.clicking = input.mouse_button_is_down(MouseButton::Left);
+        let clicked = route.clicking && !was_clicking;
+
+        if pause.paused || playback.active() {
+            return;
+        }
+
+        if input.keys_that_are_down().next().is_some() {
+            if route.active() {
+                route.clear();
+            }
+            return;
+        }
+
+        if !clicked {
+            return;
+        }
+
+        let mouse = match input.mouse_position() {
+            Some(mouse) => mouse,
+            None => return,
+        };
+
+        // everyone shares the one camera, riding along with the videographer
+        let videographer = (&cameras, &parents)
+            .join()
+            .next()
+            .and_then(|(camera, parent)| Some((camera, transforms.get(parent.entity)?)));
+        let (x, y) = match videographer {
+            Some((camera, videographer)) => screen_to_world(
+                camera,
+                videographer,
+                mouse,
+                (screen.width(), screen.height()),
+            ),
+            None => return,
+        };
+
+        let visible = graph
+            .nodes
+            .iter()
+            .any(|node| node.contains(x, y) && fog.revealed.contains_key(&node.key));
+        if !visible {
+            return;
+        }
+
+        if let Some((_, _, transform)) = (&balls, &players, &transforms)
+            .join()
+            .find(|(_, player, _)| player.index == 0)
+        {
+            // no planning a way through a gate that's still locked
+            let locked: HashSet<String> = path_segments
+                .iter()
+                .filter(|segment| !inventory.opens(segment))
+                .map(|segment| segment_key(&segment.transform))
+                .collect();
+            let from = (transform.translation().x, transform.translation().y);
+            if route.plan(&graph, from, (x, y), |node| !locked.contains(&node.key)) {
+                log::info!("rolling to ({}, {})", x, y);
+            }
+        }
+    }
+}
+
+/// Rolls the first player's ball along a clicked route by putting it in the controls, so the
+/// route's recorded and replayed like anything pressed on the keyboard
+#[derive(SystemDesc)]
+pub struct RouteSystem;
+
+impl<'s> System<'s> for RouteSystem {
+    type SystemData = (
+        Write<'s, Route>,
+        Write<'s, Controls>,
+        ReadStorage<'s, Transform>,
+        ReadStorage<'s, Ball>,
+        ReadStorage<'s, Player>,
+        Read<'s, SegmentGraph>,
+        Read<'s, Playback>,
+        Read<'s, Pause>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            mut route,
+            mut controls,
+            transforms,
+            balls,
+            players,
+            graph,
+            playback,
+            pause,
+        ): Self::SystemData,
+    ) {
+        if !route.active() || pause.paused || playback.active() {
+            return;
+        }
+
+        if let Some((_, _, transform)) = (&balls, &players, &transforms)
+            .join()
+            .find(|(_, player, _)| player.index == 0)
+        {
+            let movement =
+                route.steer(&graph, transform.translation().x, transform.translation().y);
+
+            if controls.movement.is_empty() {
+                controls.movement.push(movement);
+            } else {
+                controls.movement[0] = movement;
+            }
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use amethyst::{
+    core::{Hidden, Transform},
+    derive::SystemDesc,
+    ecs::{Entities, Join, Read, ReadStorage, System, SystemData, Write, WriteStorage},
+};
+
+use crate::components::{
+    ball::Ball,
+    collectible::{Collectible, Collection},
+    fog::FogOfWar,
+    player::Player,
+    shapes::circle::Circle,
+};
+
+#[derive(SystemDesc)]
+pub struct CollectibleSystem;
+
+impl<'s> System<'s> for CollectibleSystem {
+    type SystemData = (
+        Entities<'s>,
+        WriteStorage<'s, Collectible>,
+        WriteStorage<'s, Hidden>,
+        ReadStorage<'s, Transform>,
+        ReadStorage<'s, Circle>,
+        ReadStorage<'s, Ball>,
+        ReadStorage<'s, Player>,
+        Read<'s, FogOfWar>,
+        Write<'s, Collection>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            entities,
+            mut collectibles,
+            mut hidden_things,
+            transforms,
+            circles,
+            balls,
+            players,
+            fog,
+            mut collection,
+        ): Self::SystemData,
+    ) {
+        let ball_positions: Vec<(f32, f32, f32)> = (&balls, &players, &circles, &transforms)
+            .join()
+            .map(|(_, _, circle, transform)| {
+                (
+                    transform.translation().x,
+                    transform.translation().y,
+                    circle.radius,
+                )
+            })
+            .collect();
+
+        let mut collected = 0;
+        let mut total = 0;
+
+        for (entity, collectible, circle, transform) in
+            (&entities, &mut collectibles, &circles, &transforms).join()
+        {
+            if !collectible.collected {
+                let x = transform.translation().x;
+                let y = transform.translation().y;
+                collectible.collected = ball_positions.iter().any(|(ball_x, ball_y, radius)| {
+                    let reach = radius + circle.radius;
+                    (x - ball_x).powi(2) + (y - ball_y).powi(2) <= reach * reach
+                });
+            }
+
+            if collectible.collected || !fog.revealed.contains_key(&collectible.segment) {
+                let _ = hidden_things.insert(entity, Hidden);
+            } else {
+                let _ = hidden_things.remove(entity);
+            }
+
+            if collectible.collected {
+                collected += 1;
+            }
+            total += 1;
+        }
+
+        collection.collected = collected;
+        collection.total = total;
+    }
+}
@@ -0,0 +1,81 @@
+use amethyst::{
+    core::Time,
+    derive::SystemDesc,
+    ecs::{Read, System, SystemData, Write},
+    input::InputHandler,
+};
+
+use crate::components::player::{Player, MAX_PLAYERS};
+use crate::config::WanderballConfig;
+use crate::resources::{
+    bindings::{ActionBinding, AxisBinding, GameBindings},
+    controls::{Controls, Movement, Playback, Recording},
+    pause::Pause,
+};
+
+/// Decides what the controls are this frame, either from the keyboard or from a replay
+#[derive(SystemDesc)]
+pub struct ControlsSystem;
+
+impl<'s> System<'s> for ControlsSystem {
+    type SystemData = (
+        Write<'s, Controls>,
+        Write<'s, Playback>,
+        Read<'s, Pause>,
+        Read<'s, WanderballConfig>,
+        Read<'s, InputHandler<GameBindings>>,
+        Read<'s, Time>,
+    );
+
+    fn run(&mut self, (mut controls, mut playback, pause, config, input, time): Self::SystemData) {
+        // nothing moves while the game is covered up
+        if pause.paused {
+            *controls = Controls::default();
+            return;
+        }
+
+        if playback.active() {
+            match playback.frames.get(playback.frame) {
+                Some(frame) => {
+                    *controls = frame.clone();
+                    playback.frame += 1;
+                    // the keyboard takes over from the end of the replay
+                    if playback.finished() {
+                        log::info!("replay finished");
+                        *playback = Playback::default();
+                    }
+                }
+                None => *controls = Controls::default(),
+            }
+        } else {
+            *controls = Controls {
+                movement: (0..config.players.clamp(1, MAX_PLAYERS))
+                    .map(Player::new)
+                    .map(|player| Movement {
+                        move_x: input.axis_value(&AxisBinding::MoveX(player.index)),
+                        move_y: input.axis_value(&AxisBinding::MoveY(player.index)),
+                    })
+                    .collect(),
+                zoom: input.axis_value(&AxisBinding::Zoom),
+                fast_movement: input.action_is_down(&ActionBinding::FastMovement),
+                delta_seconds: time.delta_seconds(),
+            };
+        }
+    }
+}
+
+/// Records the controls once everything that steers has had its say, the autopilot and a
+/// clicked route included, so a replay rolls the ball the same way
+#[derive(SystemDesc)]
+pub struct RecordingSystem;
+
+impl<'s> System<'s> for RecordingSystem {
+    type SystemData = (Read<'s, Controls>, Write<'s, Recording>, Read<'s, Pause>);
+
+    fn run(&mut self, (controls, mut recording, pause): Self::SystemData) {
+        // paused frames aren't worth recording
+        if recording.active && !pause.paused {
+            recording.replay.frames.push(controls.clone());
+        }
+    }
+}
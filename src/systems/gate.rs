@@ -0,0 +1,83 @@
+use amethyst::{
+    core::{Hidden, Transform},
+    derive::SystemDesc,
+    ecs::{Entities, Join, Read, ReadStorage, System, SystemData, Write, WriteStorage},
+};
+
+use crate::components::{
+    ball::Ball,
+    fog::FogOfWar,
+    gate::{Inventory, Key},
+    player::Player,
+    shapes::circle::Circle,
+};
+use crate::config::WanderballConfig;
+
+/// Picks keys up when a player rolls over them, gates open as soon as their key's in the
+/// inventory
+#[derive(SystemDesc)]
+pub struct KeySystem;
+
+impl<'s> System<'s> for KeySystem {
+    type SystemData = (
+        Entities<'s>,
+        WriteStorage<'s, Key>,
+        WriteStorage<'s, Hidden>,
+        ReadStorage<'s, Transform>,
+        ReadStorage<'s, Circle>,
+        ReadStorage<'s, Ball>,
+        ReadStorage<'s, Player>,
+        Read<'s, FogOfWar>,
+        Read<'s, WanderballConfig>,
+        Write<'s, Inventory>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            entities,
+            mut keys,
+            mut hidden_things,
+            transforms,
+            circles,
+            balls,
+            players,
+            fog,
+            config,
+            mut inventory,
+        ): Self::SystemData,
+    ) {
+        let ball_positions: Vec<(f32, f32, f32)> = (&balls, &players, &circles, &transforms)
+            .join()
+            .map(|(_, _, circle, transform)| {
+                (
+                    transform.translation().x,
+                    transform.translation().y,
+                    circle.radius,
+                )
+            })
+            .collect();
+
+        for (entity, key, transform) in (&entities, &mut keys, &transforms).join() {
+            if !key.collected {
+                let x = transform.translation().x;
+                let y = transform.translation().y;
+                key.collected = ball_positions.iter().any(|(ball_x, ball_y, radius)| {
+                    let reach = radius + config.collectible_radius;
+                    (x - ball_x).powi(2) + (y - ball_y).powi(2) <= reach * reach
+                });
+
+                if key.collected && !inventory.has(key.color) {
+                    log::info!("picked up the {} key", key.color.name());
+                    inventory.keys.push(key.color);
+                }
+            }
+
+            if key.collected || !fog.revealed.contains_key(&key.segment) {
+                let _ = hidden_things.insert(entity, Hidden);
+            } else {
+                let _ = hidden_things.remove(entity);
+            }
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use amethyst::{
+    core::Transform,
+    derive::SystemDesc,
+    ecs::{Join, Read, ReadStorage, System, SystemData, Write, WriteStorage},
+};
+
+use crate::components::{ball::Ball, ghost::Ghost, player::Player};
+use crate::resources::{controls::Recording, pause::Pause};
+
+/// Writes down where the ball is for the recording, and walks any ghost through where a
+/// previous ball was on the same frame
+#[derive(SystemDesc)]
+pub struct GhostSystem;
+
+impl<'s> System<'s> for GhostSystem {
+    type SystemData = (
+        WriteStorage<'s, Transform>,
+        WriteStorage<'s, Ghost>,
+        ReadStorage<'s, Ball>,
+        ReadStorage<'s, Player>,
+        Write<'s, Recording>,
+        Read<'s, Pause>,
+    );
+
+    fn run(
+        &mut self,
+        (mut transforms, mut ghosts, balls, players, mut recording, pause): Self::SystemData,
+    ) {
+        if pause.paused {
+            return;
+        }
+
+        if recording.active {
+            // the ghost only ever follows the first player
+            if let Some((_, _, transform)) = (&balls, &players, &transforms)
+                .join()
+                .find(|(_, player, _)| player.index == 0)
+            {
+                let position = [transform.translation().x, transform.translation().y];
+                recording.replay.positions.push(position);
+            }
+        }
+
+        for (ghost, transform) in (&mut ghosts, &mut transforms).join() {
+            if let Some([x, y]) = ghost.positions.get(ghost.frame).copied() {
+                transform.set_translation_x(x);
+                transform.set_translation_y(y);
+                ghost.frame += 1;
+            }
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use amethyst::{
+    core::{Hidden, Transform},
+    derive::SystemDesc,
+    ecs::{Entities, Join, Read, ReadStorage, System, SystemData, WriteStorage},
+};
+
+use std::collections::HashMap;
+
+use crate::components::{
+    ball::Ball,
+    fog::FogOfWar,
+    hazard::{Checkpoint, Hazard, LastCheckpoint},
+    path::{segment_key, PathSegment},
+    player::Player,
+    shapes::{
+        circle::Circle,
+        rectangle::{distance_to_rect, Rectangle},
+    },
+    surface::Momentum,
+    wanderdata::Pedometer,
+};
+use crate::resources::{controls::Controls, pause::Pause};
+
+/// Remembers the last checkpoint segment each player rolled onto
+#[derive(SystemDesc)]
+pub struct CheckpointSystem;
+
+impl<'s> System<'s> for CheckpointSystem {
+    type SystemData = (
+        ReadStorage<'s, PathSegment>,
+        ReadStorage<'s, Checkpoint>,
+        ReadStorage<'s, Transform>,
+        ReadStorage<'s, Player>,
+        ReadStorage<'s, Pedometer>,
+        WriteStorage<'s, LastCheckpoint>,
+    );
+
+    fn run(
+        &mut self,
+        (segments, checkpoints, transforms, players, pedometers, mut last_checkpoints): Self::SystemData,
+    ) {
+        let checkpoint_positions: HashMap<String, (f32, f32)> =
+            (&segments, &checkpoints, &transforms)
+                .join()
+                .map(|(_, _, transform)| {
+                    (
+                        segment_key(transform),
+                        (transform.translation().x, transform.translation().y),
+                    )
+                })
+                .collect();
+
+        if checkpoint_positions.is_empty() {
+            return;
+        }
+
+        for (_, pedometer, last_checkpoint) in (&players, &pedometers, &mut last_checkpoints).join()
+        {
+            if let Some((x, y)) = pedometer
+                .current_segment
+                .as_ref()
+                .and_then(|segment| checkpoint_positions.get(segment))
+            {
+                last_checkpoint.x = *x;
+                last_checkpoint.y = *y;
+            }
+        }
+    }
+}
+
+/// Slides hazards along their patrols and sends any player ball that touches one back to its
+/// last checkpoint
+#[derive(SystemDesc)]
+pub struct HazardSystem;
+
+impl<'s> System<'s> for HazardSystem {
+    type SystemData = (
+        Entities<'s>,
+        WriteStorage<'s, Hazard>,
+        WriteStorage<'s, Transform>,
+        WriteStorage<'s, Hidden>,
+        WriteStorage<'s, Momentum>,
+        ReadStorage<'s, Rectangle>,
+        ReadStorage<'s, Ball>,
+        ReadStorage<'s, Player>,
+        ReadStorage<'s, Circle>,
+        ReadStorage<'s, LastCheckpoint>,
+        Read<'s, FogOfWar>,
+        Read<'s, Pause>,
+        Read<'s, Controls>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            entities,
+            mut hazards,
+            mut transforms,
+            mut hidden_things,
+            mut momenta,
+            rectangles,
+            balls,
+            players,
+            circles,
+            last_checkpoints,
+            fog,
+            pause,
+            controls,
+        ): Self::SystemData,
+    ) {
+        if pause.paused {
+            return;
+        }
+
+        let mut hazard_areas: Vec<(Transform, Rectangle)> = vec![];
+
+        for (entity, hazard, rectangle, transform) in
+            (&entities, &mut hazards, &rectangles, &mut transforms).join()
+        {
+            hazard.advance(controls.delta_seconds);
+            let (x, y) = hazard.patrol.position(hazard.phase);
+            transform.set_translation_x(x);
+            transform.set_translation_y(y);
+
+            // only ones the fog's lifted from can be seen, or bumped into
+            if fog.revealed.contains_key(&hazard.segment) {
+                let _ = hidden_things.remove(entity);
+                hazard_areas.push((transform.clone(), rectangle.clone()));
+            } else {
+                let _ = hidden_things.insert(entity, Hidden);
+            }
+        }
+
+        if hazard_areas.is_empty() {
+            return;
+        }
+
+        for (entity, _, _, circle, last_checkpoint, transform) in (
+            &entities,
+            &balls,
+            &players,
+            &circles,
+            &last_checkpoints,
+            &mut transforms,
+        )
+            .join()
+        {
+            let x = transform.translation().x;
+            let y = transform.translation().y;
+            let hit = hazard_areas.iter().any(|(hazard_transform, rectangle)| {
+                distance_to_rect(x, y, hazard_transform, rectangle) < circle.radius
+            });
+
+            if hit {
+                log::info!("ball hit a hazard, back to the last checkpoint");
+                transform.set_translation_x(last_checkpoint.x);
+                transform.set_translation_y(last_checkpoint.y);
+                if let Some(momentum) = momenta.get_mut(entity) {
+                    *momentum = Momentum::default();
+                }
+            }
+        }
+    }
+}
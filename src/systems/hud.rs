@@ -0,0 +1,111 @@
+use amethyst::{
+    core::Hidden,
+    derive::SystemDesc,
+    ecs::{Entities, Join, Read, ReadStorage, System, SystemData, WriteStorage},
+    ui::UiText,
+    utils::fps_counter::FpsCounter,
+};
+
+use crate::components::{
+    collectible::Collection,
+    gate::{Inventory, Key},
+    hud::{HudSource, HudWidget},
+    player::Player,
+    speedrun::Speedrun,
+    wanderdata::{Coordinate, Pedometer},
+};
+use crate::config::WanderballConfig;
+use crate::resources::locale::Locale;
+
+/// Fills in every widget on the HUD from whatever it's showing
+#[derive(SystemDesc)]
+pub struct HudSystem;
+
+impl<'s> System<'s> for HudSystem {
+    type SystemData = (
+        Entities<'s>,
+        ReadStorage<'s, HudWidget>,
+        WriteStorage<'s, UiText>,
+        WriteStorage<'s, Hidden>,
+        ReadStorage<'s, Player>,
+        ReadStorage<'s, Pedometer>,
+        ReadStorage<'s, Key>,
+        Read<'s, Coordinate>,
+        Read<'s, Pedometer>,
+        Read<'s, Collection>,
+        Read<'s, Speedrun>,
+        Read<'s, Inventory>,
+        Read<'s, FpsCounter>,
+        Read<'s, WanderballConfig>,
+        Read<'s, Locale>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            entities,
+            widgets,
+            mut ui_text,
+            mut hidden_things,
+            players,
+            player_pedometers,
+            keys,
+            coordinates,
+            pedometer,
+            collection,
+            speedrun,
+            inventory,
+            fps,
+            config,
+            locale,
+        ): Self::SystemData,
+    ) {
+        // levels without any gates have no inventory worth showing
+        let has_keys = keys.join().next().is_some();
+
+        for (entity, widget, text) in (&entities, &widgets, &mut ui_text).join() {
+            // turned off in the settings
+            if !config.show_hud {
+                let _ = hidden_things.insert(entity, Hidden);
+                continue;
+            }
+            let _ = hidden_things.remove(entity);
+
+            text.text = match widget.source {
+                HudSource::Coordinates => format!("({},{})", coordinates.x, coordinates.y),
+                HudSource::Steps => locale.format("hud.steps", &[&pedometer.steps]),
+                HudSource::Stats => pedometer.stats(&locale),
+                HudSource::Exploration => locale.format(
+                    "hud.explored",
+                    &[&format!("{:.1}", pedometer.exploration())],
+                ),
+                HudSource::Collection => {
+                    locale.format("hud.collected", &[&collection.collected, &collection.total])
+                }
+                HudSource::Timer if config.speedrun_mode => speedrun.text(&locale),
+                HudSource::Inventory if has_keys => inventory.text(&locale),
+                // only worth telling players apart when there's more than one
+                HudSource::PlayerSteps(index) if config.players > 1 => {
+                    (&players, &player_pedometers)
+                        .join()
+                        .find(|(player, _)| player.index == index)
+                        .map(|(player, pedometer)| {
+                            locale.format(
+                                "hud.player_steps",
+                                &[
+                                    &player.name(),
+                                    &pedometer.steps,
+                                    &format!("{:.1}", pedometer.exploration()),
+                                ],
+                            )
+                        })
+                        .unwrap_or_default()
+                }
+                HudSource::Fps => locale.format("hud.fps", &[&format!("{:.0}", fps.sampled_fps())]),
+                HudSource::Timer | HudSource::Inventory | HudSource::PlayerSteps(_) => {
+                    String::new()
+                }
+            };
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use amethyst::{
+    derive::SystemDesc,
+    ecs::{Join, Read, ReadStorage, System, SystemData, Write},
+};
+
+use crate::components::{level::Level, player::Player, wanderdata::Pedometer};
+use crate::config::WanderballConfig;
+
+/// Watches for any player's ball rolling onto the goal segment
+#[derive(SystemDesc)]
+pub struct GoalSystem;
+
+impl<'s> System<'s> for GoalSystem {
+    type SystemData = (
+        Write<'s, Level>,
+        ReadStorage<'s, Player>,
+        ReadStorage<'s, Pedometer>,
+        Read<'s, WanderballConfig>,
+    );
+
+    fn run(&mut self, (mut level, players, pedometers, config): Self::SystemData) {
+        if !config.goal_mode || level.complete || level.goal.is_none() {
+            return;
+        }
+
+        for (player, pedometer) in (&players, &pedometers).join() {
+            if pedometer.current_segment == level.goal {
+                log::info!(
+                    "{} reached the goal of level {}",
+                    player.name(),
+                    level.number
+                );
+                level.complete = true;
+                break;
+            }
+        }
+    }
+}
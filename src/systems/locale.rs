@@ -0,0 +1,50 @@
+use amethyst::{
+    derive::SystemDesc,
+    ecs::{Entities, Join, Read, System, SystemData, WriteStorage},
+    ui::UiText,
+};
+
+use crate::components::localized::Localized;
+use crate::resources::locale::Locale;
+
+/// Swaps string keys in ui text for the current language's strings as soon as they show up, and
+/// again whenever the language changes
+#[derive(SystemDesc)]
+pub struct LocaleSystem;
+
+impl<'s> System<'s> for LocaleSystem {
+    type SystemData = (
+        Entities<'s>,
+        WriteStorage<'s, UiText>,
+        WriteStorage<'s, Localized>,
+        Read<'s, Locale>,
+    );
+
+    fn run(&mut self, (entities, mut ui_text, mut localized_things, locale): Self::SystemData) {
+        for (entity, text) in (&entities, &mut ui_text).join() {
+            match localized_things.get_mut(entity) {
+                Some(localized) if localized.revision != locale.revision => {
+                    if text.text == localized.text {
+                        text.text = locale.text(&localized.key);
+                        localized.text = text.text.clone();
+                    }
+                    localized.revision = locale.revision;
+                }
+                Some(_) => {}
+                None if locale.has(&text.text) => {
+                    let key = text.text.clone();
+                    text.text = locale.text(&key);
+                    let _ = localized_things.insert(
+                        entity,
+                        Localized {
+                            key,
+                            revision: locale.revision,
+                            text: text.text.clone(),
+                        },
+                    );
+                }
+                None => {}
+            }
+        }
+    }
+}
@@ -1,10 +1,38 @@
+pub use self::autopilot::AutopilotSystem;
 pub use self::ball::BallSystem;
+pub use self::click::{ClickSystem, RouteSystem};
+pub use self::collectible::CollectibleSystem;
+pub use self::controls::{ControlsSystem, RecordingSystem};
+pub use self::gate::KeySystem;
+pub use self::ghost::GhostSystem;
+pub use self::hazard::{CheckpointSystem, HazardSystem};
+pub use self::hud::HudSystem;
+pub use self::level::GoalSystem;
+pub use self::locale::LocaleSystem;
+pub use self::npc::NpcSystem;
 pub use self::path::PathSegmentSystem;
 pub use self::path::PathSystem;
+pub use self::player::RaceSystem;
+pub use self::portal::PortalSystem;
+pub use self::speedrun::SpeedrunSystem;
 pub use self::videographer::VideographerSystem;
-pub use self::wanderdata::CoordinateSystem;
+pub use self::wanderdata::{CoordinateSystem, StatsSystem};
 
+pub mod autopilot;
 pub mod ball;
+pub mod click;
+pub mod collectible;
+pub mod controls;
+pub mod gate;
+pub mod ghost;
+pub mod hazard;
+pub mod hud;
+pub mod level;
+pub mod locale;
+pub mod npc;
 pub mod path;
+pub mod player;
+pub mod portal;
+pub mod speedrun;
 pub mod videographer;
 pub mod wanderdata;
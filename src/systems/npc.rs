@@ -0,0 +1,156 @@
+use amethyst::{
+    core::{Hidden, Transform},
+    derive::SystemDesc,
+    ecs::{Entities, Join, Read, ReadStorage, System, SystemData, Write, WriteStorage},
+};
+
+use rand::seq::SliceRandom;
+
+use crate::components::{
+    ball::Ball,
+    fog::FogOfWar,
+    npc::{Behavior, Npc, NpcRng},
+    player::Player,
+};
+use crate::config::WanderballConfig;
+use crate::resources::{controls::Controls, graph::SegmentGraph, pause::Pause};
+
+/// How close an NPC has to get to the middle of a segment before it moves on to the next one
+const ARRIVED: f32 = 0.5;
+
+/// Picks where each NPC ball is headed, plans a route there over the segment graph and rolls it
+/// along, NPCs only show up once the fog's lifted from where they are
+#[derive(SystemDesc)]
+pub struct NpcSystem;
+
+impl<'s> System<'s> for NpcSystem {
+    type SystemData = (
+        Entities<'s>,
+        WriteStorage<'s, Npc>,
+        WriteStorage<'s, Transform>,
+        WriteStorage<'s, Hidden>,
+        ReadStorage<'s, Ball>,
+        ReadStorage<'s, Player>,
+        Read<'s, SegmentGraph>,
+        Read<'s, FogOfWar>,
+        Read<'s, Pause>,
+        Read<'s, Controls>,
+        Write<'s, NpcRng>,
+        Read<'s, WanderballConfig>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            entities,
+            mut npcs,
+            mut transforms,
+            mut hidden_things,
+            balls,
+            players,
+            graph,
+            fog,
+            pause,
+            controls,
+            mut rng,
+            config,
+        ): Self::SystemData,
+    ) {
+        if pause.paused || graph.nodes.is_empty() {
+            return;
+        }
+
+        let leader = (&balls, &players, &transforms)
+            .join()
+            .find(|(_, player, _)| player.index == 0)
+            .and_then(|(_, _, transform)| {
+                graph.node_at(transform.translation().x, transform.translation().y)
+            });
+
+        let rng = &mut rng.0;
+
+        for (entity, npc, transform, _) in (&entities, &mut npcs, &mut transforms, &balls).join() {
+            let current = match graph.node_at(transform.translation().x, transform.translation().y)
+            {
+                Some(current) => current,
+                None => continue,
+            };
+
+            // following NPCs replan whenever the player has moved on from where they were headed
+            if npc.behavior == Behavior::FollowPlayer {
+                let leader_key = leader.map(|leader| graph.nodes[leader].key.clone());
+                if leader_key != npc.destination {
+                    npc.route.clear();
+                }
+            }
+
+            if npc.route.is_empty() {
+                let destination = match npc.behavior {
+                    Behavior::RandomWalk => {
+                        let neighbours = graph.neighbours(current);
+                        let onward: Vec<usize> = neighbours
+                            .iter()
+                            .copied()
+                            .filter(|neighbour| {
+                                Some(&graph.nodes[*neighbour].key) != npc.previous.as_ref()
+                            })
+                            .collect();
+                        // dead ends are the only time it's allowed to double back
+                        if onward.is_empty() {
+                            neighbours.choose(rng).copied()
+                        } else {
+                            onward.choose(rng).copied()
+                        }
+                    }
+                    Behavior::SeekUnexplored => {
+                        let visited = &npc.visited;
+                        graph
+                            .nearest(current, |node| !visited.contains_key(&node.key))
+                            // once it's been everywhere it just keeps wandering
+                            .or_else(|| graph.neighbours(current).choose(rng).copied())
+                    }
+                    Behavior::FollowPlayer => leader,
+                };
+
+                npc.destination =
+                    destination.map(|destination| graph.nodes[destination].key.clone());
+                if let Some(path) = destination
+                    .and_then(|destination| graph.find_path(current, destination, |_| true))
+                {
+                    npc.route = path
+                        .into_iter()
+                        .skip(1)
+                        .map(|node| graph.nodes[node].key.clone())
+                        .collect();
+                }
+            }
+
+            if let Some(next) = npc.route.first().and_then(|key| graph.node(key)) {
+                let target = &graph.nodes[next];
+                let x = transform.translation().x;
+                let y = transform.translation().y;
+                let (dx, dy) = (target.x - x, target.y - y);
+                let distance = (dx * dx + dy * dy).sqrt();
+                let step = config.npc_speed * controls.delta_seconds;
+
+                // going through a portal is a jump straight to the other end
+                if distance <= step.max(ARRIVED) || graph.is_portal(current, next) {
+                    transform.set_translation_x(target.x);
+                    transform.set_translation_y(target.y);
+                    npc.previous = Some(graph.nodes[current].key.clone());
+                    npc.visited.insert(target.key.clone(), ());
+                    npc.route.remove(0);
+                } else {
+                    transform.set_translation_x(x + dx / distance * step);
+                    transform.set_translation_y(y + dy / distance * step);
+                }
+            }
+
+            if fog.revealed.contains_key(&graph.nodes[current].key) {
+                let _ = hidden_things.remove(entity);
+            } else {
+                let _ = hidden_things.insert(entity, Hidden);
+            }
+        }
+    }
+}
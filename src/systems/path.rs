@@ -1,15 +1,20 @@
 use amethyst::{
     core::{Hidden, Transform},
     derive::SystemDesc,
-    ecs::{Entities, Join, Read, ReadStorage, System, SystemData, WriteStorage},
-    renderer::{palette::Srgba, resources::Tint},
+    ecs::{Entities, Join, Read, ReadStorage, System, SystemData, Write, WriteStorage},
+    renderer::resources::Tint,
 };
 
 use crate::components::{
     ball::Ball,
+    fog::FogOfWar,
+    hazard::Checkpoint,
     path::{Path, PathSegment},
+    player::Player,
+    portal::Portal,
     shapes::circle::Circle,
-    shapes::rectangle::{point_in_rect, Rectangle},
+    shapes::rectangle::{distance_to_rect, point_in_rect, Rectangle},
+    surface::Surface,
     videographer::Videographer,
 };
 
@@ -40,8 +45,13 @@ impl<'s> System<'s> for PathSegmentSystem {
         ReadStorage<'s, Rectangle>,
         WriteStorage<'s, Tint>,
         ReadStorage<'s, Ball>,
+        ReadStorage<'s, Player>,
         ReadStorage<'s, Circle>,
         ReadStorage<'s, Videographer>,
+        ReadStorage<'s, Surface>,
+        ReadStorage<'s, Checkpoint>,
+        ReadStorage<'s, Portal>,
+        Write<'s, FogOfWar>,
         Read<'s, WanderballConfig>,
     );
 
@@ -55,9 +65,14 @@ impl<'s> System<'s> for PathSegmentSystem {
             rectangles,
             mut tints,
             balls,
+            players,
             circles,
             videographers,
-            _config,
+            surfaces,
+            checkpoints,
+            portals,
+            mut fog,
+            config,
         ): Self::SystemData,
     ) {
         let mut curr_view_height = 0.0;
@@ -93,21 +108,48 @@ impl<'s> System<'s> for PathSegmentSystem {
         for (_, rectangle, tint, entity, transform) in
             (&segments, &rectangles, &mut tints, &entities, &transforms).join()
         {
-            for (_ball, circle, ball_transform) in (&balls, &circles, &transforms).join() {
-                if point_in_rect(
-                    ball_transform.translation().x,
-                    ball_transform.translation().y,
-                    left(transform, rectangle, circle),
-                    bottom(transform, rectangle, circle),
-                    right(transform, rectangle, circle),
-                    top(transform, rectangle, circle),
-                ) {
-                    *tint = Tint(Srgba::new(0.95, 0.95, 0.95, 1.0));
+            // only players lift the fog, NPCs wander about underneath it
+            for (_ball, _, circle, ball_transform) in
+                (&balls, &players, &circles, &transforms).join()
+            {
+                // checkpoints and portals keep their colour so they're easy to find again
+                if !checkpoints.contains(entity)
+                    && !portals.contains(entity)
+                    && point_in_rect(
+                        ball_transform.translation().x,
+                        ball_transform.translation().y,
+                        left(transform, rectangle, circle),
+                        bottom(transform, rectangle, circle),
+                        right(transform, rectangle, circle),
+                        top(transform, rectangle, circle),
+                    )
+                {
+                    *tint = surfaces
+                        .get(entity)
+                        .copied()
+                        .unwrap_or_default()
+                        .visited_tint();
+                }
+
+                if !fog.is_revealed(transform)
+                    && distance_to_rect(
+                        ball_transform.translation().x,
+                        ball_transform.translation().y,
+                        transform,
+                        rectangle,
+                    ) <= config.reveal_radius
+                {
+                    fog.reveal(transform);
                 }
             }
             let x = transform.translation().x;
             let y = transform.translation().y;
-            if x > max_x_val || x < min_x_val || y > max_y_val || y < min_y_max {
+            if !fog.is_revealed(transform)
+                || x > max_x_val
+                || x < min_x_val
+                || y > max_y_val
+                || y < min_y_max
+            {
                 let _ = hidden_things.insert(entity, Hidden);
             } else {
                 let _ = hidden_things.remove(entity);
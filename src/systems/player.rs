@@ -0,0 +1,41 @@
+use amethyst::{
+    derive::SystemDesc,
+    ecs::{Join, Read, ReadStorage, System, SystemData, Write},
+};
+
+use crate::components::{
+    level::Level,
+    player::{Player, Race},
+    wanderdata::Pedometer,
+};
+use crate::config::WanderballConfig;
+
+/// With more than one player, ends the level when someone has explored enough of the path to win
+#[derive(SystemDesc)]
+pub struct RaceSystem;
+
+impl<'s> System<'s> for RaceSystem {
+    type SystemData = (
+        ReadStorage<'s, Player>,
+        ReadStorage<'s, Pedometer>,
+        Write<'s, Race>,
+        Write<'s, Level>,
+        Read<'s, WanderballConfig>,
+    );
+
+    fn run(&mut self, (players, pedometers, mut race, mut level, config): Self::SystemData) {
+        let racing = config.players > 1;
+
+        for (player, pedometer) in (&players, &pedometers).join() {
+            if racing
+                && race.winner.is_none()
+                && !level.complete
+                && pedometer.exploration() >= config.race_exploration
+            {
+                log::info!("{} wins the race", player.name());
+                race.winner = Some(*player);
+                level.complete = true;
+            }
+        }
+    }
+}
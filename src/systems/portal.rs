@@ -0,0 +1,92 @@
+use amethyst::{
+    core::Transform,
+    derive::SystemDesc,
+    ecs::{Join, Read, ReadStorage, System, SystemData, WriteStorage},
+};
+
+use crate::autopilot::Autopilot;
+use crate::components::{
+    ball::Ball,
+    player::Player,
+    portal::{PortalCooldown, Portals},
+    surface::Momentum,
+};
+use crate::config::WanderballConfig;
+use crate::resources::{controls::Controls, pause::Pause};
+
+/// Sends player balls that roll into the middle of a portal out of the other end
+#[derive(SystemDesc)]
+pub struct PortalSystem;
+
+impl<'s> System<'s> for PortalSystem {
+    type SystemData = (
+        WriteStorage<'s, Transform>,
+        WriteStorage<'s, PortalCooldown>,
+        WriteStorage<'s, Momentum>,
+        ReadStorage<'s, Ball>,
+        ReadStorage<'s, Player>,
+        Read<'s, Portals>,
+        Read<'s, Autopilot>,
+        Read<'s, Pause>,
+        Read<'s, Controls>,
+        Read<'s, WanderballConfig>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            mut transforms,
+            mut cooldowns,
+            mut momenta,
+            balls,
+            players,
+            portals,
+            autopilot,
+            pause,
+            controls,
+            config,
+        ): Self::SystemData,
+    ) {
+        if pause.paused || portals.pairs.is_empty() {
+            return;
+        }
+
+        for (_, player, transform, cooldown, momentum) in (
+            &balls,
+            &players,
+            &mut transforms,
+            &mut cooldowns,
+            (&mut momenta).maybe(),
+        )
+            .join()
+        {
+            // the autopilot rolls the path as it was laid down
+            if autopilot.active && player.index == 0 {
+                continue;
+            }
+
+            let x = transform.translation().x;
+            let y = transform.translation().y;
+            match portals.exit_from(x, y, config.portal_radius) {
+                Some(exit) if cooldown.remaining <= 0.0 => {
+                    log::info!(
+                        "{} went through a portal to {}",
+                        player.name(),
+                        exit.segment
+                    );
+                    transform.set_translation_x(exit.x);
+                    transform.set_translation_y(exit.y);
+                    cooldown.remaining = config.portal_cooldown;
+                    if let Some(momentum) = momentum {
+                        *momentum = Momentum::default();
+                    }
+                }
+                // sat on the far end, it can go back once it's rolled off and the cooldown's up
+                Some(_) => {}
+                None => {
+                    cooldown.remaining = (cooldown.remaining - controls.delta_seconds).max(0.0);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use amethyst::{
+    core::Time,
+    derive::SystemDesc,
+    ecs::{Read, System, SystemData, Write},
+};
+
+use crate::components::{
+    level::Level,
+    speedrun::{format_timer, Speedrun},
+    wanderdata::Pedometer,
+};
+use crate::config::WanderballConfig;
+use crate::resources::{
+    pause::Pause,
+    records::{read_records, write_records, PersonalBestRecord},
+};
+
+#[derive(SystemDesc)]
+pub struct SpeedrunSystem;
+
+impl<'s> System<'s> for SpeedrunSystem {
+    type SystemData = (
+        Write<'s, Speedrun>,
+        Read<'s, Pedometer>,
+        Read<'s, Level>,
+        Read<'s, Pause>,
+        Read<'s, Time>,
+        Read<'s, WanderballConfig>,
+    );
+
+    fn run(&mut self, (mut speedrun, pedometer, level, pause, time, config): Self::SystemData) {
+        if !config.speedrun_mode || speedrun.finished || pause.paused {
+            return;
+        }
+
+        // the clock starts as soon as the ball rolls anywhere
+        if !speedrun.running {
+            if pedometer.distance > 0.0 {
+                speedrun.running = true;
+            } else {
+                return;
+            }
+        }
+
+        speedrun.elapsed += time.delta_seconds();
+
+        if config.speedrun_split_interval > 0.0 {
+            let explored = pedometer.exploration();
+            while explored >= (speedrun.splits.len() + 1) as f32 * config.speedrun_split_interval {
+                let elapsed = speedrun.elapsed;
+                speedrun.splits.push(elapsed);
+            }
+        }
+
+        if level.complete || pedometer.exploration() >= 100.0 {
+            speedrun.running = false;
+            speedrun.finished = true;
+            log::info!("speedrun finished in {}", format_timer(speedrun.elapsed));
+
+            let beat_best = match &speedrun.personal_best {
+                Some(personal_best) => speedrun.elapsed < personal_best.time,
+                None => true,
+            };
+            if speedrun.eligible && beat_best {
+                let personal_best = PersonalBestRecord {
+                    time: speedrun.elapsed,
+                    splits: speedrun.splits.clone(),
+                };
+                let mut records = read_records();
+                records
+                    .personal_bests
+                    .insert(level.seed.to_string(), personal_best.clone());
+                write_records(&records);
+                speedrun.personal_best = Some(personal_best);
+                log::info!("new personal best for seed {}", level.seed);
+            }
+        }
+    }
+}
@@ -2,15 +2,16 @@ use amethyst::{
     core::transform::Transform,
     derive::SystemDesc,
     ecs::{Join, Read, ReadStorage, System, SystemData, WriteStorage},
-    input::{InputHandler, StringBindings},
     renderer::Camera,
 };
 
 use crate::camera::zoom;
 use crate::components::ball::Ball;
+use crate::components::player::Player;
 use crate::components::shapes::rectangle::point_outside_rect;
-use crate::components::videographer::Videographer;
+use crate::components::videographer::{CameraMode, Videographer};
 use crate::config::WanderballConfig;
+use crate::resources::controls::Controls;
 use crate::side::Side;
 
 #[derive(SystemDesc, Default)]
@@ -22,21 +23,27 @@ impl<'s> System<'s> for VideographerSystem {
         WriteStorage<'s, Camera>,
         WriteStorage<'s, Videographer>,
         ReadStorage<'s, Ball>,
+        ReadStorage<'s, Player>,
         Read<'s, WanderballConfig>,
-        Read<'s, InputHandler<StringBindings>>,
+        Read<'s, Controls>,
     );
 
     fn run(
         &mut self,
-        (mut transforms, mut cameras, mut videographers, balls, config, input): Self::SystemData,
+        (mut transforms, mut cameras, mut videographers, balls, players, config, controls): Self::SystemData,
     ) {
-        let mut ball_x = 0.0;
-        let mut ball_y = 0.0;
-        // Get the local position of the ball.
-        for (_ball, transform) in (&balls, &transforms).join() {
-            ball_x = transform.translation().x as f32;
-            ball_y = transform.translation().y as f32;
+        // Everyone shares the one view, so it keeps to the middle of the players' balls.
+        let ball_positions: Vec<(f32, f32)> = (&balls, &players, &transforms)
+            .join()
+            .map(|(_, _, transform)| (transform.translation().x, transform.translation().y))
+            .collect();
+        if ball_positions.is_empty() {
+            return;
         }
+        let ball_x =
+            ball_positions.iter().map(|(x, _)| x).sum::<f32>() / ball_positions.len() as f32;
+        let ball_y =
+            ball_positions.iter().map(|(_, y)| y).sum::<f32>() / ball_positions.len() as f32;
 
         let left = |videographer: &Videographer| -> f32 {
             videographer.view_x - (videographer.view_width * 0.5)
@@ -51,7 +58,7 @@ impl<'s> System<'s> for VideographerSystem {
             videographer.view_y + (videographer.view_height * 0.5)
         };
 
-        let fast_zoom = input.action_is_down("fast_movement");
+        let fast_zoom = controls.fast_movement;
 
         let (mut curr_view_width, mut curr_view_height) = (0.0, 0.0);
         for (videographer, _) in (&videographers, &transforms).join() {
@@ -68,7 +75,7 @@ impl<'s> System<'s> for VideographerSystem {
         let mut new_view_width;
 
         for (_, camera) in (&transforms, &mut cameras).join() {
-            if let Some(zoom_input) = input.axis_value("zoom") {
+            if let Some(zoom_input) = controls.zoom {
                 if zoom_input > 0.0 {
                     if let Some(true) = fast_zoom {
                         new_view_height = (curr_view_height - fast_zoom_factor).max(100.0);
@@ -101,7 +108,21 @@ impl<'s> System<'s> for VideographerSystem {
                 videographer.view_width = new_width;
             }
 
-            if let Some(side) = point_outside_rect(
+            // somewhere further than the next view over, through a portal or back to a
+            // checkpoint, gets jumped to in one go rather than flipping over a view a frame
+            let views_x = ((ball_x - videographer.view_x) / videographer.view_width).round();
+            let views_y = ((ball_y - videographer.view_y) / videographer.view_height).round();
+            if config.camera_mode == CameraMode::Follow {
+                transform.set_translation_xyz(ball_x, ball_y, 2.0);
+                videographer.view_x = ball_x;
+                videographer.view_y = ball_y;
+            } else if views_x.abs() > 1.0 || views_y.abs() > 1.0 {
+                let new_x = videographer.view_x + views_x * videographer.view_width;
+                let new_y = videographer.view_y + views_y * videographer.view_height;
+                transform.set_translation_xyz(new_x, new_y, 2.0);
+                videographer.view_x = new_x;
+                videographer.view_y = new_y;
+            } else if let Some(side) = point_outside_rect(
                 ball_x,
                 ball_y,
                 left(videographer),
@@ -1,12 +1,14 @@
 use amethyst::{
-    core::Transform,
+    core::{Time, Transform},
     derive::SystemDesc,
-    ecs::{Join, ReadExpect, System, SystemData, Write, WriteStorage},
-    ui::UiText,
+    ecs::{Join, Read, ReadStorage, System, SystemData, Write, WriteStorage},
 };
 
 use crate::components::ball::Ball;
-use crate::components::wanderdata::{Coordinate, CoordinateText};
+use crate::components::player::Player;
+use crate::components::wanderdata::{Coordinate, Pedometer};
+use crate::resources::pause::Pause;
+use crate::resources::session::Session;
 
 #[derive(SystemDesc)]
 pub struct CoordinateSystem;
@@ -15,26 +17,39 @@ impl<'s> System<'s> for CoordinateSystem {
     type SystemData = (
         WriteStorage<'s, Ball>,
         WriteStorage<'s, Transform>,
-        WriteStorage<'s, UiText>,
+        ReadStorage<'s, Player>,
         Write<'s, Coordinate>,
-        ReadExpect<'s, CoordinateText>,
+        Write<'s, Session>,
     );
 
     fn run(
         &mut self,
-        (mut balls, mut transforms, mut ui_text, mut coordinates, coordinate_text): Self::SystemData,
+        (mut balls, mut transforms, players, mut coordinates, mut session): Self::SystemData,
     ) {
-        for (_, transform) in (&mut balls, &mut transforms).join() {
+        // the coordinates follow the first player around
+        for (_, transform, _) in (&mut balls, &mut transforms, &players)
+            .join()
+            .filter(|(_, _, player)| player.index == 0)
+        {
             coordinates.x = transform.translation().x;
             coordinates.y = transform.translation().y;
+            session.track(coordinates.x, coordinates.y);
+        }
+    }
+}
 
-            if let Some(text) = ui_text.get_mut(coordinate_text.coordinates) {
-                text.text = format!(
-                    "({},{})",
-                    coordinates.x.to_string(),
-                    coordinates.y.to_string()
-                );
-            }
+#[derive(SystemDesc)]
+pub struct StatsSystem;
+
+impl<'s> System<'s> for StatsSystem {
+    type SystemData = (Write<'s, Pedometer>, Read<'s, Pause>, Read<'s, Time>);
+
+    fn run(&mut self, (mut pedometer, pause, time): Self::SystemData) {
+        // nothing to keep track of until there's a path to wander
+        if pedometer.segment_count == 0 || pause.paused {
+            return;
         }
+
+        pedometer.time_played += time.delta_seconds();
     }
 }